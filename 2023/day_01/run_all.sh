@@ -14,12 +14,18 @@ echo '-------------------------------------------------------------------------'
 echo "Python: $(cd ./python && time python main.py)"
 echo "Julia: $(cd ./julia && time julia main.jl)"
 echo "C++: $(cd ./cpp && time ./main)"
-echo "Rust: $(cd ./rust && time ./target/release/main)"
+echo "Rust: $(cd ./rust && time ../../../target/release/day_01_trebuchet)"
 # replacement
 echo "Python: $(cd ./python && time python main.py --replace)"
 echo "Julia: $(cd ./julia && time julia main.jl --replace)"
 echo "C++: $(cd ./cpp && time ./main --replace)"
-echo "Rust: $(cd ./rust && time ./target/release/main --replace)"
+echo "Rust: $(cd ./rust && time ../../../target/release/day_01_trebuchet --replace)"
+# in-process benchmark of the Rust stages, without process startup and file I/O
+echo ''
+echo '-------------------------------------------------------------------------'
+echo 'Running benchmark...'
+echo '-------------------------------------------------------------------------'
+(cd ../.. && cargo run --release -q -p aoc -- bench 2023 1)
 echo ''
 echo '-------------------------------------------------------------------------'
 echo 'Comparison complete.'
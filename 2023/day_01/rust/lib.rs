@@ -0,0 +1,1843 @@
+//!
+//! FILENAME
+//!   AdventOfCode/2023/day_01/rust/lib.rs
+//!
+//! DESCRIPTION
+//!   December 01: The Trebuchet Problem
+//!
+//! AUTHOR
+//!   Adam Erickson, PhD
+//!
+//! DATE
+//!   2023-12-01
+//!
+//! NOTES
+//!   -
+//!
+
+use std::borrow::Cow;
+use std::fmt::Display;
+use std::path::Path;
+use std::sync::OnceLock;
+use std::thread;
+
+use aoc_common::{
+    colorize, line_chunks, read_input, Accumulator, Answer, Error, Location, Part, Result, Runner,
+    Solution,
+};
+
+/*
+ * Modules
+ *
+ * Forward and reversed byte-level tries of the digit words, in place of the Aho-Corasick crate,
+ * which reports every match where only the first and last are needed.
+ */
+pub mod matcher;
+
+use matcher::Matcher;
+
+/*
+ * Crates
+ *
+ * Arbitrary-precision unsigned integers for totals beyond `u128`, with the `bigint` feature.
+ */
+#[cfg(feature = "bigint")]
+use num_bigint::BigUint;
+
+///
+/// Variables, Data Structures, Types
+///
+
+/*
+ * English digit words. The digit value of each word is its index plus one.
+ */
+pub const WORDS: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+/* Number of slots in the perfect hash table of `WORDS`. */
+const SLOTS: usize = 16;
+
+/* Lengths of the shortest and longest of `WORDS`, the only lengths worth looking up. */
+const SHORTEST_WORD: usize = word_len(&WORDS, false);
+const LONGEST_WORD: usize = word_len(&WORDS, true);
+
+/*
+ * Perfect hash table of `WORDS`, generated at compile time: each word hashes to its own slot,
+ * which holds the word's digit value. Empty slots hold 0, which is no word's value. The same idea
+ * as `static_map.hpp` of the C++ solution, without the linear key search.
+ */
+pub struct DigitTable {
+    seed: u64,
+    slots: [u8; SLOTS],
+}
+
+/*
+ * English word-to-digit lookup of `Vocab::english`, used wherever a vocabulary looks up a word
+ * at an index: the reference `calibration_value`, and every summation and replacement method but
+ * the trie ones, which match with their own automaton. See `DigitTable`.
+ */
+pub const DIGIT_TABLE: DigitTable = DigitTable::build();
+
+/*
+ * Line summation function: (line, vocab, replace, verbose) -> calibration value, if the line has a
+ * digit.
+ */
+pub type LineSumFn = fn(&str, &Vocab, bool, bool) -> Option<u32>;
+
+/* Whole-line word replacement function: (line, vocab, verbose) -> line with digits. */
+pub type WordsFn = fn(&str, &Vocab, bool) -> String;
+
+/*
+ * Word-to-digit table used to recognise spelled-out digits, and the radix of digit chars.
+ * Defaults to English words and decimal digits; other languages and alias lists are loaded from a
+ * file. Words are kept longest first, so where two words start at the same index the longer one
+ * wins. A digit char always wins over a word starting at the same index, so words that start with
+ * a digit of the radix are dropped. With `roman`, maximal runs of the letters I, V, and X that
+ * spell a numeral below the radix are read as one token. With `unicode`, lines are read by char:
+ * any Unicode decimal digit counts, and words match regardless of case. While the words are
+ * exactly the English `WORDS`, matched by case, they are looked up in `DIGIT_TABLE`.
+ */
+#[derive(Clone, Debug)]
+pub struct Vocab {
+    words: Vec<(String, u32)>,
+    radix: u32,
+    roman: bool,
+    roman_requested: bool,
+    unicode: bool,
+    english: bool,
+    matcher: OnceLock<Matcher>,
+}
+
+/* Roman numeral letters and values, largest first. */
+static ROMAN: [(&str, u32); 5] = [("X", 10), ("IX", 9), ("V", 5), ("IV", 4), ("I", 1)];
+
+/*
+ * Zero of each run of ten Unicode decimal digits (general category Nd), in code point order, as
+ * of Unicode 16.0. `char::is_numeric` also accepts fractions, superscripts, and Roman numeral
+ * symbols, which have no single digit value, so the value of a digit is its offset from a zero.
+ */
+static DECIMAL_ZEROS: [u32; 76] = [
+    0x0030, 0x0660, 0x06F0, 0x07C0, 0x0966, 0x09E6, 0x0A66, 0x0AE6, 0x0B66, 0x0BE6, 0x0C66, 0x0CE6,
+    0x0D66, 0x0DE6, 0x0E50, 0x0ED0, 0x0F20, 0x1040, 0x1090, 0x17E0, 0x1810, 0x1946, 0x19D0, 0x1A80,
+    0x1A90, 0x1B50, 0x1BB0, 0x1C40, 0x1C50, 0xA620, 0xA8D0, 0xA900, 0xA9D0, 0xA9F0, 0xAA50, 0xABF0,
+    0xFF10, 0x104A0, 0x10D30, 0x10D40, 0x11066, 0x110F0, 0x11136, 0x111D0, 0x112F0, 0x11450,
+    0x114D0, 0x11650, 0x116C0, 0x116D0, 0x116DA, 0x11730, 0x118E0, 0x11950, 0x11BF0, 0x11C50,
+    0x11D50, 0x11DA0, 0x11F50, 0x16130, 0x16A60, 0x16AC0, 0x16B50, 0x16D70, 0x1CCF0, 0x1D7CE,
+    0x1D7D8, 0x1D7E2, 0x1D7EC, 0x1D7F6, 0x1E140, 0x1E2F0, 0x1E4F0, 0x1E5F1, 0x1E950, 0x1FBF0,
+];
+
+/* Which tokens count as digits: digit chars only (part one), or spelled-out words too (part two). */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Mode {
+    Digits,
+    Words,
+}
+
+/*
+ * Line summation methods and word replacement strategies by name. "array" has no whole-line word
+ * replacement function: the summation method replaces words in place instead.
+ */
+const LINE_SUM_FNS: [(&str, LineSumFn); 4] = [
+    ("array", line_sum_array),
+    ("iterator", line_sum_iterator),
+    ("simd", line_sum_simd),
+    ("trie", line_sum_trie),
+];
+const WORDS_FNS: [(&str, Option<WordsFn>); 3] = [
+    ("array", None),
+    ("builtin", Some(words_to_digits_builtin)),
+    ("trie", Some(words_to_digits_trie)),
+];
+
+/* Names accepted by `line_sum_fn_str` and `words_fn_str`. */
+pub static SUM_METHODS: [&str; 4] = method_names(&LINE_SUM_FNS);
+pub static WORDS_METHODS: [&str; 3] = method_names(&WORDS_FNS);
+
+/* SWAR constants: a byte of ones in every lane, and the high bit and low seven bits of each lane. */
+const LANES: u64 = 0x0101_0101_0101_0101;
+const HIGH_BITS: u64 = 0x8080_8080_8080_8080;
+const LOW_BITS: u64 = 0x7f7f_7f7f_7f7f_7f7f;
+
+/* Integer types the total can be kept in. `big` needs the `bigint` feature. */
+#[cfg(feature = "bigint")]
+pub static TOTALS: [&str; 3] = ["u64", "u128", "big"];
+#[cfg(not(feature = "bigint"))]
+pub static TOTALS: [&str; 2] = ["u64", "u128"];
+
+/* Integer type of the total, named as in `TOTALS`. */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Width {
+    U64,
+    U128,
+    #[cfg(feature = "bigint")]
+    Big,
+}
+
+/* What to do with a line without a digit, and with an empty line. */
+pub static MISSING_POLICIES: [&str; 3] = ["error", "skip", "zero"];
+pub static EMPTY_POLICIES: [&str; 2] = ["error", "skip"];
+
+/* Policy for a line without a digit, named as in `MISSING_POLICIES`. */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OnMissing {
+    Error,
+    Skip,
+    Zero,
+}
+
+/* Policy for an empty line, named as in `EMPTY_POLICIES`. */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OnEmpty {
+    Error,
+    Skip,
+}
+
+/* Number of lines each policy applied to while summing. */
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Tally {
+    pub missing: usize,
+    pub empty: usize,
+}
+
+/*
+ * Running total of calibration values. Fixed-width totals are added with checked arithmetic, so
+ * a total too large for its type is an error rather than silently wrapping.
+ */
+pub trait Total: Clone + Display + Send + Sized {
+    const NAME: &'static str;
+
+    fn zero() -> Self;
+
+    fn from_value(value: u32) -> Self;
+
+    /* Sum of two totals, or `None` if it does not fit the type. */
+    fn checked_add(&self, other: &Self) -> Option<Self>;
+
+    /* Answer for the total: an integer in decimal, or its digits in any other radix. */
+    fn answer(&self, vocab: &Vocab) -> Answer;
+}
+
+/*
+ * Day 1 solution. The line summation method and the word replacement strategy used by part two
+ * are selected at construction. Without a whole-line `words_to_digits` function, the summation
+ * method replaces words in place while it scans. With `check`, every line's value is compared
+ * against `calibration_value`. With more than one thread, lines are summed in contiguous chunks.
+ * The total is kept in the integer type `total`. Empty lines and lines without a digit are
+ * handled by `on_empty` and `on_missing` before and after the summation method runs, so every
+ * method treats them alike; `calibrate` returns how many lines each applied to with the total.
+ */
+#[derive(Clone)]
+pub struct Trebuchet {
+    pub line_sum: LineSumFn,
+    pub words_to_digits: Option<WordsFn>,
+    pub vocab: Vocab,
+    pub check: bool,
+    pub threads: usize,
+    pub total: Width,
+    pub on_missing: OnMissing,
+    pub on_empty: OnEmpty,
+    pub verbose: bool,
+}
+
+/*
+ * Line on which the methods disagree: the reference `calibration_value`, and the value computed
+ * by each combination of methods, in the order of `Trebuchet::combinations`.
+ */
+#[derive(Clone, Debug)]
+pub struct LineDiff<'a> {
+    pub line: usize,
+    pub text: &'a str,
+    pub expected: Option<u32>,
+    pub values: Vec<Option<u32>>,
+}
+
+/* Running sum of calibration values for `aoc run --follow`. */
+pub struct Calibration<T: Total> {
+    solution: Trebuchet,
+    replace: bool,
+    lines: usize,
+    sum: T,
+    tally: Tally,
+}
+
+///
+/// Functions
+///
+
+impl Vocab {
+    /* Table of word-value pairs for decimal digits, sorted longest word first. */
+    pub fn new(mut words: Vec<(String, u32)>) -> Vocab {
+        words.sort_by_key(|(word, _)| std::cmp::Reverse(word.len()));
+        let vocab: Vocab = Vocab {
+            words,
+            radix: 10,
+            roman: false,
+            roman_requested: false,
+            unicode: false,
+            english: false,
+            matcher: OnceLock::new(),
+        };
+        return vocab.with_radix(10);
+    }
+
+    /*
+     * Drop words that can never be read: a digit char wins over a word at the same index. The
+     * words or radix changed, so any matcher built for the old ones is dropped too, and
+     * `DIGIT_TABLE` only stands in for the words while none were dropped and case matters. Roman
+     * numerals are only read while the letter I is not a digit of the radix.
+     */
+    fn retain_readable(&mut self) {
+        self.matcher = OnceLock::new();
+        self.roman = self.roman_requested && 'I'.to_digit(self.radix).is_none();
+        let radix: u32 = self.radix;
+        let unicode: bool = self.unicode;
+        self.words.retain(|(word, value)| {
+            let first: char = word.chars().next().unwrap_or_default();
+            return *value < radix && digit_value(first, radix, unicode).is_none();
+        });
+        self.english = self.english && !unicode && self.words.len() == WORDS.len();
+    }
+
+    /*
+     * Read digit chars in the given radix, 2 to 36, where the letters a-z (either case) are the
+     * digits 10 to 35. Words whose value is not a digit of the radix, or which start with one,
+     * can never be read and are dropped.
+     */
+    pub fn with_radix(mut self, radix: u32) -> Vocab {
+        self.radix = radix;
+        self.retain_readable();
+        return self;
+    }
+
+    /*
+     * Read lines by char rather than by byte: any Unicode decimal digit, such as fullwidth "１"
+     * or Arabic-Indic "٣", is a digit, and words match regardless of case. Words are stored in
+     * lowercase, keeping the first of any that collide.
+     */
+    pub fn with_unicode(mut self, unicode: bool) -> Vocab {
+        self.unicode = unicode;
+        if unicode {
+            let mut words: Vec<(String, u32)> = Vec::new();
+            for (word, value) in &self.words {
+                let lower: String = word.to_lowercase();
+                if !words.iter().any(|(w, _)| *w == lower) {
+                    words.push((lower, *value));
+                }
+            }
+            words.sort_by_key(|(word, _)| std::cmp::Reverse(word.len()));
+            self.words = words;
+        }
+        self.retain_readable();
+        return self;
+    }
+
+    /* Whether lines are read by char, with Unicode digits and case-insensitive words. */
+    pub fn unicode(&self) -> bool {
+        return self.unicode;
+    }
+
+    /*
+     * Also read Roman numerals as digits. Ignored above radix 18, where the letter I is itself a
+     * digit, whether the radix is set before or after.
+     */
+    pub fn with_roman(mut self, roman: bool) -> Vocab {
+        self.roman_requested = roman;
+        self.retain_readable();
+        return self;
+    }
+
+    /* Trie matcher of the words and ASCII digit chars, built on the first call. */
+    pub fn matcher(&self) -> &Matcher {
+        return self
+            .matcher
+            .get_or_init(|| Matcher::new(&self.patterns(), self.words.len()));
+    }
+
+    /* Words followed by the ASCII digit chars of the radix in either case, with their values. */
+    fn patterns(&self) -> Vec<(String, u32)> {
+        let mut patterns: Vec<(String, u32)> = self.words.clone();
+        for value in 0..self.radix {
+            let c: char = char::from_digit(value, self.radix).unwrap_or_default();
+            for case in [c, c.to_ascii_uppercase()] {
+                if !patterns.iter().any(|(p, _)| *p == case.to_string()) {
+                    patterns.push((case.to_string(), value));
+                }
+            }
+        }
+        return patterns;
+    }
+
+    /* Radix of digit chars and calibration values. */
+    pub fn radix(&self) -> u32 {
+        return self.radix;
+    }
+
+    /* Value of a digit char in the radix. */
+    pub fn digit(&self, c: char) -> Option<u32> {
+        return digit_value(c, self.radix, self.unicode);
+    }
+
+    /*
+     * Char starting at byte `index` of the line. Bytes are chars unless reading Unicode, where an
+     * index inside a multi-byte char, or on an invalid byte, has no char.
+     */
+    pub fn char_at(&self, line: &[u8], index: usize) -> Option<char> {
+        if !self.unicode {
+            return Some(u8_to_char(line[index]));
+        }
+        return line[index..].utf8_chunks().next()?.valid().chars().next();
+    }
+
+    /* Whether the line has the word at byte `index`, ignoring case when reading Unicode. */
+    pub fn matches_word(&self, line: &[u8], index: usize, word: &str) -> bool {
+        if !self.unicode {
+            return line[index..].starts_with(word.as_bytes());
+        }
+        let valid: &str = match line[index..].utf8_chunks().next() {
+            Some(chunk) => chunk.valid(),
+            None => return false,
+        };
+        let mut lower = valid.chars().flat_map(char::to_lowercase);
+        return word.chars().all(|c| lower.next() == Some(c));
+    }
+
+    /* ASCII byte of a digit value in the radix. */
+    pub fn digit_byte(&self, value: u32) -> u8 {
+        return char::from_digit(value, self.radix).map_or(b'?', |c| c as u8);
+    }
+
+    /* Concatenate two digits into a calibration value. */
+    pub fn concat(&self, left: u32, right: u32) -> u32 {
+        return (left * self.radix) + right;
+    }
+
+    /* Format a value in the radix, e.g. 175 as "af" in radix 16. */
+    pub fn format(&self, value: impl Into<u128>) -> String {
+        let radix: u128 = u128::from(self.radix);
+        let mut digits: Vec<char> = Vec::new();
+        let mut rest: u128 = value.into();
+        loop {
+            let digit: u32 = (rest % radix) as u32; // below the radix, at most 35
+            digits.push(char::from_digit(digit, self.radix).unwrap_or('?'));
+            rest /= radix;
+            if rest == 0 {
+                break;
+            }
+        }
+        return digits.iter().rev().collect();
+    }
+
+    /*
+     * English digit words "one" to "nine", with their values from `DIGIT_TABLE`, which also looks
+     * them up in lines.
+     */
+    pub fn english() -> Vocab {
+        let words: Vec<(String, u32)> = WORDS
+            .into_iter()
+            .filter_map(|word| Some((String::from(word), DIGIT_TABLE.get(word.as_bytes())?)))
+            .collect();
+        let mut vocab: Vocab = Vocab::new(words);
+        vocab.english = true;
+        return vocab;
+    }
+
+    /*
+     * Parse a vocabulary file: one `WORD VALUE` pair per line, separated by whitespace, where
+     * VALUE is a digit written in decimal, 0 to 35. Values of 10 and up are only read with a
+     * large enough radix. Blank lines and lines starting with `#` are ignored.
+     */
+    pub fn parse(text: &str) -> Result<Vocab> {
+        let mut words: Vec<(String, u32)> = Vec::new();
+        for (i, line) in text.lines().enumerate() {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.is_empty() || fields[0].starts_with('#') {
+                continue;
+            }
+            // 1-based char column of a field within the line
+            let column = |field: &str| -> usize {
+                let start: usize = field.as_ptr() as usize - line.as_ptr() as usize;
+                return line[..start].chars().count() + 1;
+            };
+            if fields.len() != 2 {
+                return Err(Error::parse(
+                    i + 1,
+                    column(fields[0]),
+                    "expected `WORD VALUE`",
+                ));
+            }
+            let value: u32 = match fields[1].parse::<u32>() {
+                Ok(v) if v < 36 => v,
+                _ => {
+                    let message: &str = "value must be a digit 0-35";
+                    return Err(Error::parse(i + 1, column(fields[1]), message));
+                }
+            };
+            if words.iter().any(|(word, _)| word == fields[0]) {
+                let message: String = format!("duplicate word `{}`", fields[0]);
+                return Err(Error::parse(i + 1, column(fields[0]), &message));
+            }
+            words.push((String::from(fields[0]), value));
+        }
+        if words.is_empty() {
+            return Err(Error::Parse {
+                location: Location::default(),
+                message: String::from("vocabulary has no words"),
+            });
+        }
+        return Ok(Vocab::new(words));
+    }
+
+    /* Load a vocabulary file. Errors point at the offending line of the file. */
+    pub fn load(path: &Path) -> Result<Vocab> {
+        let text: String = read_input(path)?;
+        return Vocab::parse(&text).map_err(|e| e.with_path(path));
+    }
+
+    /* Word-value pairs, longest word first. */
+    pub fn words(&self) -> &[(String, u32)] {
+        return &self.words;
+    }
+
+    /*
+     * Value of the word or Roman numeral starting at byte `index` of the line, if any. Words are
+     * compared as bytes or decoded chars, so an index inside a multi-byte char never matches and
+     * the line is never sliced mid-char.
+     */
+    pub fn word_at(&self, line: &[u8], index: usize) -> Option<u32> {
+        return self
+            .digit_word_at(line, index)
+            .or_else(|| self.roman_at(line, index).map(|(value, _)| value));
+    }
+
+    /*
+     * Value of the longest digit word starting at byte `index` of the line, if any. English words
+     * are looked up in `DIGIT_TABLE` for each word length, longest first; other vocabularies are
+     * searched in order.
+     */
+    pub fn digit_word_at(&self, line: &[u8], index: usize) -> Option<u32> {
+        if self.english {
+            return (SHORTEST_WORD..=LONGEST_WORD)
+                .rev()
+                .find_map(|len| DIGIT_TABLE.get(line.get(index..index + len)?));
+        }
+        return self
+            .words
+            .iter()
+            .find(|(word, _)| self.matches_word(line, index, word))
+            .map(|(_, value)| *value);
+    }
+
+    /*
+     * Value and length of the Roman numeral starting at byte `index`: a maximal run of I, V, and
+     * X in canonical form ("IV", not "IIII") whose value is a digit of the radix.
+     */
+    pub fn roman_at(&self, line: &[u8], index: usize) -> Option<(u32, usize)> {
+        let is_roman = |b: &u8| -> bool { matches!(b, b'I' | b'V' | b'X') };
+        if !self.roman || !is_roman(&line[index]) || (index > 0 && is_roman(&line[index - 1])) {
+            return None;
+        }
+        let len: usize = line[index..].iter().take_while(|b| is_roman(b)).count();
+        let run: &[u8] = &line[index..index + len];
+        // parse greedily, then require the canonical spelling of the parsed value
+        let mut value: u32 = 0;
+        let mut rest: &[u8] = run;
+        for (numeral, n) in ROMAN {
+            while rest.starts_with(numeral.as_bytes()) {
+                value += n;
+                rest = &rest[numeral.len()..];
+            }
+        }
+        if value == 0 || value >= self.radix || !rest.is_empty() || to_roman(value) != run {
+            return None;
+        }
+        return Some((value, len));
+    }
+
+    /* Digit token starting at byte `index` of the line, if any. */
+    fn digit_at(&self, line: &[u8], index: usize, mode: Mode) -> Option<u32> {
+        if let Some(value) = self.char_at(line, index).and_then(|c| self.digit(c)) {
+            return Some(value);
+        }
+        if mode == Mode::Words {
+            return self.word_at(line, index);
+        }
+        return None;
+    }
+
+    /*
+     * Calibration value of a line: the first digit token followed by the last one, or `None` if
+     * the line has none. Both ends are searched independently on the unmodified line, so
+     * overlapping words resolve correctly: "eightwo" is 82 and "twone" is 21. This is the
+     * reference that every summation and word replacement method is checked against.
+     */
+    pub fn calibration_value(&self, line: &str, mode: Mode) -> Option<u32> {
+        let bytes: &[u8] = line.as_bytes();
+        let first: u32 = (0..bytes.len()).find_map(|i| self.digit_at(bytes, i, mode))?;
+        let last: u32 = (0..bytes.len())
+            .rev()
+            .find_map(|i| self.digit_at(bytes, i, mode))?;
+        return Some(self.concat(first, last));
+    }
+}
+
+impl DigitTable {
+    /*
+     * Try seeds until every word of `WORDS` hashes to a slot of its own. Runs at compile time; a
+     * failed search is a compile error.
+     */
+    const fn build() -> DigitTable {
+        let mut seed: u64 = 0;
+        while seed < 1 << 16 {
+            let mut slots: [u8; SLOTS] = [0; SLOTS];
+            let mut i: usize = 0;
+            while i < WORDS.len() {
+                let slot: usize = DigitTable::hash(WORDS[i].as_bytes(), seed);
+                if slots[slot] != 0 {
+                    break;
+                }
+                slots[slot] = (i + 1) as u8;
+                i += 1;
+            }
+            if i == WORDS.len() {
+                return DigitTable { seed, slots };
+            }
+            seed += 1;
+        }
+        panic!("no perfect hash seed for WORDS");
+    }
+
+    /*
+     * FNV-1a hash of a word, offset by the seed, reduced to a slot. Short words differ in few bits
+     * of an FNV hash, so the halves are mixed with a Fibonacci multiply before taking the slot.
+     */
+    const fn hash(word: &[u8], seed: u64) -> usize {
+        let mut hash: u64 = 0xcbf2_9ce4_8422_2325 ^ seed;
+        let mut i: usize = 0;
+        while i < word.len() {
+            hash ^= word[i] as u64;
+            hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+            i += 1;
+        }
+        hash ^= hash >> 32;
+        hash = hash.wrapping_mul(0x9e37_79b9_7f4a_7c15);
+        return ((hash >> 32) % SLOTS as u64) as usize;
+    }
+
+    /* Digit value of an English digit word, or `None` for any other bytes. */
+    pub const fn get(&self, word: &[u8]) -> Option<u32> {
+        let value: u8 = self.slots[DigitTable::hash(word, self.seed)];
+        if value == 0 {
+            return None;
+        }
+        let expected: &[u8] = WORDS[value as usize - 1].as_bytes();
+        if expected.len() != word.len() {
+            return None;
+        }
+        let mut i: usize = 0;
+        while i < word.len() {
+            if word[i] != expected[i] {
+                return None;
+            }
+            i += 1;
+        }
+        return Some(value as u32);
+    }
+}
+
+/* Length of the longest word, or of the shortest without `longest`. */
+const fn word_len(words: &[&str], longest: bool) -> usize {
+    let mut len: usize = words[0].len();
+    let mut i: usize = 1;
+    while i < words.len() {
+        if (longest && words[i].len() > len) || (!longest && words[i].len() < len) {
+            len = words[i].len();
+        }
+        i += 1;
+    }
+    return len;
+}
+
+impl Default for Vocab {
+    fn default() -> Vocab {
+        return Vocab::english();
+    }
+}
+
+/*
+ * Value of a digit char in the radix. With `unicode`, non-ASCII decimal digits are read too, by
+ * their offset from the zero of their run.
+ */
+fn digit_value(c: char, radix: u32, unicode: bool) -> Option<u32> {
+    if c.is_ascii() || !unicode {
+        return c.to_digit(radix);
+    }
+    let code: u32 = u32::from(c);
+    let run: usize = DECIMAL_ZEROS.partition_point(|zero| *zero <= code);
+    let zero: u32 = *DECIMAL_ZEROS.get(run.checked_sub(1)?)?;
+    let value: u32 = code - zero;
+    if value < 10 && value < radix {
+        return Some(value);
+    }
+    return None;
+}
+
+/*
+ * Canonical Roman numeral of a value, e.g. 9 as "IX".
+ */
+fn to_roman(value: u32) -> Vec<u8> {
+    let mut numeral: Vec<u8> = Vec::new();
+    let mut rest: u32 = value;
+    for (letters, n) in ROMAN {
+        while rest >= n {
+            numeral.extend_from_slice(letters.as_bytes());
+            rest -= n;
+        }
+    }
+    return numeral;
+}
+
+/*
+ * Calibration value of a line with the English vocabulary. See `Vocab::calibration_value`.
+ */
+pub fn calibration_value(line: &str, mode: Mode) -> Option<u32> {
+    static ENGLISH: OnceLock<Vocab> = OnceLock::new();
+    return ENGLISH
+        .get_or_init(Vocab::english)
+        .calibration_value(line, mode);
+}
+
+/*
+ * Encode u8 to char (ASCII/UTF-8).
+ */
+fn u8_to_char(bytes: u8) -> char {
+    let c: char = char::from(bytes); // every byte is a valid Unicode scalar value
+    return c;
+}
+
+/*
+ * Find and replace the word or Roman numeral starting at byte `index` with its digit. The outer
+ * function increments the index. Tokens are looked up on the unmodified `line`, so an earlier
+ * replacement never moves the boundaries of a later one, and only the first byte of a token is
+ * overwritten in `line_bytes`, which is copied from the line the first time a token is found.
+ */
+pub fn words_to_digits_array(
+    line: &[u8],
+    line_bytes: &mut Cow<[u8]>,
+    index: usize,
+    vocab: &Vocab,
+    verbose: bool,
+) {
+    // looking up through the vocabulary bounds-checks the slice and ignores case when reading
+    // Unicode
+    if let Some(value) = vocab.word_at(line, index) {
+        line_bytes.to_mut()[index] = vocab.digit_byte(value);
+        if verbose {
+            println!("updated: {}", String::from_utf8_lossy(line_bytes));
+        }
+    }
+}
+
+/*
+ * Find and replace integer words with digits. Idiomatic built-in method.
+ * The digit is inserted after the first char of each word ("eight" -> "e8ight"), so every char is
+ * kept and a word that overlaps it by one letter ("eightwo" -> "e8igh2two") is still found. Words
+ * and Roman numerals are matched in one left-to-right pass over the unmodified line, as in
+ * `calibration_value`, so neither hides the other and the case of the line is never changed.
+ */
+pub fn words_to_digits_builtin(line: &str, vocab: &Vocab, verbose: bool) -> String {
+    if verbose {
+        println!("line: {}", line);
+    }
+    let result: String = line
+        .char_indices()
+        .flat_map(|(i, c)| {
+            let digit: Option<char> = match vocab.digit(c) {
+                Some(_) => None, // a digit char wins over a word at the same index
+                None => vocab.word_at(line.as_bytes(), i),
+            }
+            .map(|value| char::from(vocab.digit_byte(value)));
+            return std::iter::once(c).chain(digit);
+        })
+        .collect();
+    if verbose {
+        println!("updated: {}", result);
+    }
+    return result;
+}
+
+/*
+ * Find and replace integer words with digits, using the vocabulary's trie `Matcher`. The longest
+ * word starting at each index of the unmodified line is matched, and only the first byte of each
+ * word or Roman numeral is replaced, so overlapping tokens ("twone" -> "2w1ne") all survive.
+ * The trie matches bytes exactly, so case-insensitive Unicode words fall back to
+ * `words_to_digits_builtin`.
+ */
+pub fn words_to_digits_trie(line: &str, vocab: &Vocab, verbose: bool) -> String {
+    if vocab.unicode() {
+        return words_to_digits_builtin(line, vocab, verbose);
+    }
+    if verbose {
+        println!("line: {}", line);
+    }
+    let bytes: &[u8] = line.as_bytes();
+    let mut result: Vec<u8> = Vec::from(bytes);
+    let matcher: &Matcher = vocab.matcher();
+    for (i, byte) in result.iter_mut().enumerate() {
+        let value: Option<u32> = matcher
+            .word_at(bytes, i)
+            .or_else(|| vocab.roman_at(bytes, i).map(|(value, _)| value));
+        if let Some(value) = value {
+            *byte = vocab.digit_byte(value);
+        }
+    }
+    if verbose {
+        println!("updated: {}", String::from_utf8_lossy(&result));
+    }
+    return String::from_utf8_lossy(&result).to_string();
+}
+
+/*
+ * Loop over line string (char array) and return the sum of outermost digits, or `None` if the
+ * line has no digit. We do not use mutability on left or right, but the compiler complains anyway.
+ * Convert string to bytes and loop over indices (the C way) not iterators (the Rust way). The
+ * bytes are borrowed from the line, and only copied the first time a word or numeral is found
+ * and replaced in place; words and numerals are always looked up on the unmodified line.
+ */
+#[allow(unused_mut)]
+pub fn line_sum_array(line: &str, vocab: &Vocab, replace: bool, verbose: bool) -> Option<u32> {
+    // variables
+    let n: usize; // line string length
+    let mut c: char; // char
+    let mut left: u32; // left digit value
+    let mut right: u32; // right digit value
+    let mut sum: u32; // sum of first and last digit chars
+    let mut line_bytes: Cow<[u8]> = Cow::Borrowed(line.as_bytes());
+    if verbose {
+        println!("line: {}", line);
+    }
+    // loop over the byte array and collect digits of the radix (ASCII numbers and letters, or any
+    // decimal digit when reading Unicode, which is decoded at each char boundary)
+    n = line_bytes.len();
+    let mut j: usize = 0;
+    let mut k: usize = n.saturating_sub(1); // an empty line skips the loop
+    while j < n {
+        // check if j is start of digit word; if true, update line_bytes[j] and j in-place
+        if replace {
+            words_to_digits_array(line.as_bytes(), &mut line_bytes, j, vocab, verbose);
+        }
+        //if verbose { println!("j: {}", j); }
+        c = vocab.char_at(&line_bytes, j).unwrap_or_default();
+        //if verbose { println!("c: {}", c); }
+        if let Some(digit) = vocab.digit(c) {
+            left = digit;
+            if verbose {
+                println!("left: {}", c);
+            }
+            while k >= j {
+                // check if k is start of digit word; if true, update line_bytes[k] and k in-place
+                if replace {
+                    words_to_digits_array(line.as_bytes(), &mut line_bytes, k, vocab, verbose);
+                }
+                //println!("k: {}", k);
+                c = vocab.char_at(&line_bytes, k).unwrap_or_default();
+                //println!("c: {}", c);
+                if let Some(digit) = vocab.digit(c) {
+                    right = digit;
+                    if verbose {
+                        println!("right: {}", c);
+                    }
+                    sum = vocab.concat(left, right);
+                    if verbose {
+                        println!("{} = {}", vocab.format(sum), sum);
+                    }
+                    return Some(sum);
+                }
+                k -= 1;
+            }
+        }
+        j += 1;
+    }
+    return None;
+}
+
+/*
+ * Idiomatic Rusty variant of `line_sum` function. For comparison of readability and performance.
+ * Chars are lazily mapped to digits, so the same iterator is consumed from the left for the first
+ * digit and from the right for the last one. Words are matched at each char boundary without
+ * rewriting the line, which keeps overlapping words such as "twone" intact.
+ */
+pub fn line_sum_iterator(line: &str, vocab: &Vocab, replace: bool, verbose: bool) -> Option<u32> {
+    // variables
+    let left: u32; // left digit value
+    let right: u32; // right digit value
+    let sum: u32; // sum of first and last digit chars
+    if verbose {
+        println!("line: {}", line);
+    }
+    let mut digits = line.char_indices().filter_map(|(j, c)| {
+        if let Some(digit) = vocab.digit(c) {
+            // if character is a valid ASCII/UTF-8 digit of the radix
+            return Some(digit);
+        }
+        if replace {
+            return vocab.word_at(line.as_bytes(), j);
+        }
+        return None;
+    });
+    left = digits.next()?; // pop value from left
+    right = digits.next_back().unwrap_or(left); // pop value from right; a lone digit is both
+    if verbose {
+        println!("left: {}", left);
+        println!("right: {}", right);
+    }
+    sum = vocab.concat(left, right);
+    if verbose {
+        println!("{} = {}", vocab.format(sum), sum);
+    }
+    return Some(sum);
+}
+
+/*
+ * High bit set in each byte lane of `word` holding an ASCII digit below the radix, which is at
+ * most 10. Lanes are masked to seven bits before the additions so no lane carries into the next,
+ * and lanes with the high bit set, which are not ASCII, are excluded at the end.
+ */
+fn digit_lanes(word: u64, radix: u32) -> u64 {
+    let low: u64 = word & LOW_BITS;
+    let at_least_zero: u64 = low + LANES * u64::from(0x80 - b'0');
+    let at_least_radix: u64 = low + LANES * (0x80 - u64::from(b'0') - u64::from(radix));
+    return at_least_zero & !at_least_radix & !word & HIGH_BITS;
+}
+
+/* Whether a byte is an ASCII digit below the radix. Scalar form of `digit_lanes`. */
+fn is_digit_byte(b: u8, radix: u32) -> bool {
+    return b.is_ascii_digit() && u32::from(b - b'0') < radix;
+}
+
+/* Index of the first ASCII digit, scanning eight bytes at a time from the left. */
+fn first_digit_swar(bytes: &[u8], radix: u32) -> Option<usize> {
+    let mut chunks = bytes.chunks_exact(8);
+    for (i, chunk) in chunks.by_ref().enumerate() {
+        let word: u64 = u64::from_le_bytes(chunk.try_into().unwrap_or_default());
+        let lanes: u64 = digit_lanes(word, radix);
+        if lanes != 0 {
+            // little-endian: the lowest set lane is the leftmost byte
+            return Some(i * 8 + lanes.trailing_zeros() as usize / 8);
+        }
+    }
+    let tail: usize = bytes.len() - chunks.remainder().len();
+    let j: usize = chunks
+        .remainder()
+        .iter()
+        .position(|b| is_digit_byte(*b, radix))?;
+    return Some(tail + j);
+}
+
+/* Index of the last ASCII digit, scanning eight bytes at a time from the right. */
+fn last_digit_swar(bytes: &[u8], radix: u32) -> Option<usize> {
+    let mut chunks = bytes.rchunks_exact(8);
+    for (i, chunk) in chunks.by_ref().enumerate() {
+        let word: u64 = u64::from_le_bytes(chunk.try_into().unwrap_or_default());
+        let lanes: u64 = digit_lanes(word, radix);
+        if lanes != 0 {
+            // little-endian: the highest set lane is the rightmost byte
+            let start: usize = bytes.len() - (i + 1) * 8;
+            return Some(start + 7 - lanes.leading_zeros() as usize / 8);
+        }
+    }
+    return chunks
+        .remainder()
+        .iter()
+        .rposition(|b| is_digit_byte(*b, radix));
+}
+
+/*
+ * Word-at-a-time variant of `line_sum` function. The first and last digits are found with SWAR
+ * (SIMD within a register), testing eight bytes per step for ASCII digits. Spelled-out digits,
+ * Unicode digits, and letter digits above radix 10 fall back to the scalar `line_sum_array`.
+ */
+pub fn line_sum_simd(line: &str, vocab: &Vocab, replace: bool, verbose: bool) -> Option<u32> {
+    if replace || vocab.unicode() || vocab.radix() > 10 {
+        return line_sum_array(line, vocab, replace, verbose);
+    }
+    if verbose {
+        println!("line: {}", line);
+    }
+    let bytes: &[u8] = line.as_bytes();
+    let j: usize = first_digit_swar(bytes, vocab.radix())?;
+    let k: usize = last_digit_swar(&bytes[j..], vocab.radix())? + j;
+    let left: u32 = u32::from(bytes[j] - b'0');
+    let right: u32 = u32::from(bytes[k] - b'0');
+    let sum: u32 = vocab.concat(left, right);
+    if verbose {
+        println!("left: {}", char::from(bytes[j]));
+        println!("right: {}", char::from(bytes[k]));
+        println!("{} = {}", vocab.format(sum), sum);
+    }
+    return Some(sum);
+}
+
+/*
+ * Trie variant of `line_sum` function, using the vocabulary's `Matcher`.
+ * The first digit is the longest pattern at the leftmost index matched by the forward trie, and
+ * the last is found by the reversed trie scanning from the right, so neither end scans the whole
+ * line. Roman numerals and Unicode digits fall back to `line_sum_iterator`.
+ */
+pub fn line_sum_trie(line: &str, vocab: &Vocab, replace: bool, verbose: bool) -> Option<u32> {
+    if vocab.unicode() || (replace && vocab.roman) {
+        return line_sum_iterator(line, vocab, replace, verbose);
+    }
+    if verbose {
+        println!("line: {}", line);
+    }
+    let matcher: &Matcher = vocab.matcher();
+    let left: u32 = matcher.first(line.as_bytes(), replace)?;
+    let right: u32 = matcher.last(line.as_bytes(), replace)?;
+    let sum: u32 = vocab.concat(left, right);
+    if verbose {
+        println!("left: {}", vocab.format(left));
+        println!("right: {}", vocab.format(right));
+        println!("{} = {}", vocab.format(sum), sum);
+    }
+    return Some(sum);
+}
+
+/* Names of a table of methods, in order. */
+const fn method_names<F, const N: usize>(table: &[(&'static str, F); N]) -> [&'static str; N] {
+    let mut names: [&str; N] = [""; N];
+    let mut i: usize = 0;
+    while i < N {
+        names[i] = table[i].0;
+        i += 1;
+    }
+    return names;
+}
+
+/*
+ * Map function name string to function pointer, or `None` if no method has that name.
+ */
+pub fn line_sum_fn_str(name: &str) -> Option<LineSumFn> {
+    return LINE_SUM_FNS
+        .iter()
+        .find(|(n, _)| *n == name)
+        .map(|(_, f)| *f);
+}
+
+/*
+ * Map word replacement name string to its whole-line function, which is `None` for "array", or
+ * `None` if no strategy has that name.
+ */
+pub fn words_fn_str(name: &str) -> Option<Option<WordsFn>> {
+    return WORDS_FNS.iter().find(|(n, _)| *n == name).map(|(_, f)| *f);
+}
+
+impl Trebuchet {
+    /*
+     * Create a solution that sums lines and replaces words with the named methods. An unknown
+     * name is a usage error.
+     */
+    pub fn new(method_sum: &str, method_replace: &str, verbose: bool) -> Result<Trebuchet> {
+        let line_sum: LineSumFn = line_sum_fn_str(method_sum).ok_or_else(|| {
+            Error::Usage(format!(
+                "unknown summation method {:?} (expected one of {})",
+                method_sum,
+                SUM_METHODS.join(", ")
+            ))
+        })?;
+        let words_to_digits: Option<WordsFn> = words_fn_str(method_replace).ok_or_else(|| {
+            Error::Usage(format!(
+                "unknown word replacement method {:?} (expected one of {})",
+                method_replace,
+                WORDS_METHODS.join(", ")
+            ))
+        })?;
+        return Ok(Trebuchet::with_methods(line_sum, words_to_digits, verbose));
+    }
+
+    /* Create a solution from a summation method and word replacement function. */
+    fn with_methods(
+        line_sum: LineSumFn,
+        words_to_digits: Option<WordsFn>,
+        verbose: bool,
+    ) -> Trebuchet {
+        return Trebuchet {
+            line_sum,
+            words_to_digits,
+            vocab: Vocab::english(),
+            check: false,
+            threads: 1,
+            total: Width::U64,
+            on_missing: OnMissing::Error,
+            on_empty: OnEmpty::Error,
+            verbose,
+        };
+    }
+
+    /* Recognise spelled-out digits with the given vocabulary instead of English. */
+    pub fn with_vocab(mut self, vocab: Vocab) -> Trebuchet {
+        self.vocab = vocab;
+        return self;
+    }
+
+    /* Compare every line's value against `calibration_value`. */
+    pub fn checked(mut self, check: bool) -> Trebuchet {
+        self.check = check;
+        return self;
+    }
+
+    /* Sum lines in `threads` scoped threads. Zero uses one thread per available CPU. */
+    pub fn threaded(mut self, threads: usize) -> Trebuchet {
+        self.threads = match threads {
+            0 => thread::available_parallelism().map_or(1, |n| n.get()),
+            n => n,
+        };
+        return self;
+    }
+
+    /* Keep the total in the given integer type. */
+    pub fn with_total(mut self, total: Width) -> Trebuchet {
+        self.total = total;
+        return self;
+    }
+
+    /* Skip or count as zero lines without a digit, and skip empty lines, instead of failing. */
+    pub fn with_policies(mut self, on_missing: OnMissing, on_empty: OnEmpty) -> Trebuchet {
+        self.on_missing = on_missing;
+        self.on_empty = on_empty;
+        return self;
+    }
+
+    /*
+     * How many lines each policy other than `error` applied to, as counted by `calibrate`, such as
+     * "2 lines without a digit skipped, 1 empty line skipped", or `None` if every policy is `error`.
+     */
+    pub fn policy_summary(&self, tally: &Tally) -> Option<String> {
+        let mut affected: Vec<String> = Vec::new();
+        match self.on_missing {
+            OnMissing::Error => {}
+            OnMissing::Skip => affected.push(format!(
+                "{} without a digit skipped",
+                count_lines(tally.missing, "line")
+            )),
+            OnMissing::Zero => affected.push(format!(
+                "{} without a digit counted as 0",
+                count_lines(tally.missing, "line")
+            )),
+        }
+        if self.on_empty == OnEmpty::Skip {
+            affected.push(format!(
+                "{} skipped",
+                count_lines(tally.empty, "empty line")
+            ));
+        }
+        if affected.is_empty() {
+            return None;
+        }
+        return Some(affected.join(", "));
+    }
+
+    /*
+     * Sum the calibration values of all lines in the selected integer type, and count the lines
+     * the policies applied to.
+     */
+    pub fn calibrate(&self, text: &str, replace: bool) -> Result<(Answer, Tally)> {
+        return match self.total {
+            Width::U64 => {
+                let (sum, tally) = self.sum_lines::<u64>(text, replace)?;
+                Ok((sum.answer(&self.vocab), tally))
+            }
+            Width::U128 => {
+                let (sum, tally) = self.sum_lines::<u128>(text, replace)?;
+                Ok((sum.answer(&self.vocab), tally))
+            }
+            #[cfg(feature = "bigint")]
+            Width::Big => {
+                let (sum, tally) = self.sum_lines::<BigUint>(text, replace)?;
+                Ok((sum.answer(&self.vocab), tally))
+            }
+        };
+    }
+
+    /*
+     * Sum the calibration values of all lines, and count the lines the policies applied to. A
+     * total too large for its type is an error. Verbose output is only readable in order, so it
+     * always sums serially.
+     */
+    fn sum_lines<T: Total>(&self, text: &str, replace: bool) -> Result<(T, Tally)> {
+        if self.threads > 1 && !self.verbose {
+            return self.sum_lines_parallel(text, T::zero(), replace);
+        }
+        return self.sum_chunk(text, 1, T::zero(), replace);
+    }
+
+    /*
+     * Split the text into one chunk of whole lines per thread and sum the chunks concurrently.
+     * Chunk results are combined in line order, so the sum and the first error reported are
+     * the same as summing serially. Chunks are summed without knowing their first line number or
+     * the total before them, so a chunk with an error, or whose sum overflows the total, is
+     * summed again from where it starts to report the error at the same line. The chunks are
+     * added to the running total `start`.
+     */
+    fn sum_lines_parallel<T: Total>(
+        &self,
+        text: &str,
+        start: T,
+        replace: bool,
+    ) -> Result<(T, Tally)> {
+        let chunks: Vec<&str> = line_chunks(text, self.threads);
+        let results: Vec<Result<(T, Tally)>> = thread::scope(|scope| {
+            let handles: Vec<thread::ScopedJoinHandle<Result<(T, Tally)>>> = chunks
+                .iter()
+                .map(|chunk| scope.spawn(move || self.sum_chunk(chunk, 1, T::zero(), replace)))
+                .collect();
+            return handles
+                .into_iter()
+                .map(|h| h.join().unwrap_or_else(|e| std::panic::resume_unwind(e)))
+                .collect();
+        });
+        let mut sum: T = start;
+        let mut tally: Tally = Tally::default();
+        for (i, result) in results.into_iter().enumerate() {
+            match result
+                .ok()
+                .and_then(|(value, t)| Some((sum.checked_add(&value)?, t)))
+            {
+                Some((total, t)) => {
+                    sum = total;
+                    tally.add(t);
+                }
+                None => {
+                    let before: usize = chunks[..i].iter().map(|c| c.lines().count()).sum();
+                    return self.sum_chunk(chunks[i], before + 1, sum, replace);
+                }
+            }
+        }
+        return Ok((sum, tally));
+    }
+
+    /*
+     * Add the lines of a text, the first of which is line number `first`, to a running total.
+     */
+    fn sum_chunk<T: Total>(
+        &self,
+        text: &str,
+        first: usize,
+        start: T,
+        replace: bool,
+    ) -> Result<(T, Tally)> {
+        let mut sum: T = start; // rolling sum
+        let mut tally: Tally = Tally::default();
+        for (i, line) in text.lines().enumerate() {
+            sum = self.add_line(sum, &mut tally, first + i, line, replace)?;
+            if self.verbose {
+                println!("sum: {}", sum);
+            }
+        }
+        return Ok((sum, tally));
+    }
+
+    /*
+     * Add the calibration value of line number `n` to a total, checking for overflow. An empty
+     * line, or one without a digit, is an error unless its policy skips it or counts it as zero,
+     * in which case it is counted in `tally`.
+     */
+    fn add_line<T: Total>(
+        &self,
+        sum: T,
+        tally: &mut Tally,
+        n: usize,
+        line: &str,
+        replace: bool,
+    ) -> Result<T> {
+        if line.is_empty() {
+            match self.on_empty {
+                OnEmpty::Error => {
+                    return Err(Error::Parse {
+                        location: Location::line(n),
+                        message: String::from("empty line"),
+                    });
+                }
+                OnEmpty::Skip => {
+                    tally.empty += 1;
+                    return Ok(sum);
+                }
+            }
+        }
+        let value: u32 = match (self.line_value(n, line, replace)?, self.on_missing) {
+            (Some(value), _) => value,
+            (None, OnMissing::Error) => {
+                return Err(Error::MissingDigit {
+                    location: Location::line(n),
+                    text: String::from(line),
+                });
+            }
+            (None, OnMissing::Skip) => {
+                tally.missing += 1;
+                return Ok(sum);
+            }
+            (None, OnMissing::Zero) => {
+                tally.missing += 1;
+                0
+            }
+        };
+        return sum
+            .checked_add(&T::from_value(value))
+            .ok_or_else(|| Error::Overflow {
+                location: Location::line(n),
+                text: String::from(line),
+                message: format!(
+                    "total overflows {} adding calibration value {}",
+                    T::NAME,
+                    self.vocab.format(value)
+                ),
+            });
+    }
+
+    /* Calibration value of line number `n`, or `None` if it has no digit. */
+    fn line_value(&self, n: usize, line: &str, replace: bool) -> Result<Option<u32>> {
+        if self.verbose {
+            println!(
+                "#: {}",
+                colorize(n.to_string().as_str(), "cyan", true, false)
+            );
+        }
+        let value: Option<u32> = self.compute(line, replace);
+        if self.check {
+            let mode: Mode = if replace { Mode::Words } else { Mode::Digits };
+            let expected: Option<u32> = self.vocab.calibration_value(line, mode);
+            if value != expected {
+                return Err(Error::Mismatch {
+                    location: Location::line(n),
+                    text: String::from(line),
+                    message: format!(
+                        "calibration value {}, expected {}",
+                        value.map_or(String::from("none"), |v| self.vocab.format(v)),
+                        expected.map_or(String::from("none"), |v| self.vocab.format(v))
+                    ),
+                });
+            }
+        }
+        return Ok(value);
+    }
+
+    /* Calibration value of a line as computed by the selected methods. */
+    fn compute(&self, line: &str, replace: bool) -> Option<u32> {
+        return match self.words_to_digits {
+            Some(words_to_digits) if replace => {
+                let digits: String = words_to_digits(line, &self.vocab, self.verbose);
+                (self.line_sum)(&digits, &self.vocab, false, self.verbose)
+            }
+            _ => (self.line_sum)(line, &self.vocab, replace, self.verbose),
+        };
+    }
+
+    /*
+     * Every combination of summation method and word replacement strategy, named "sum/words",
+     * with this solution's vocabulary and settings.
+     */
+    pub fn combinations(&self) -> Vec<(String, Trebuchet)> {
+        let mut combinations: Vec<(String, Trebuchet)> = Vec::new();
+        for (method_sum, line_sum) in LINE_SUM_FNS {
+            for (method_replace, words_to_digits) in WORDS_FNS {
+                let name: String = format!("{}/{}", method_sum, method_replace);
+                let solution: Trebuchet = Trebuchet::with_methods(line_sum, words_to_digits, false)
+                    .with_vocab(self.vocab.clone())
+                    .checked(self.check)
+                    .threaded(self.threads)
+                    .with_total(self.total)
+                    .with_policies(self.on_missing, self.on_empty);
+                combinations.push((name, solution));
+            }
+        }
+        return combinations;
+    }
+
+    /*
+     * Run every combination of methods on every line, and return the combination names with the
+     * lines on which any two of them, or any one and the reference `calibration_value`, disagree.
+     * Lines are compared as they are, so empty lines and lines without a digit are included.
+     */
+    pub fn diff<'a>(&self, text: &'a str, replace: bool) -> (Vec<String>, Vec<LineDiff<'a>>) {
+        let (names, solutions): (Vec<String>, Vec<Trebuchet>) =
+            self.combinations().into_iter().unzip();
+        let mode: Mode = if replace { Mode::Words } else { Mode::Digits };
+        let mut diffs: Vec<LineDiff<'a>> = Vec::new();
+        for (i, line) in text.lines().enumerate() {
+            let expected: Option<u32> = self.vocab.calibration_value(line, mode);
+            let values: Vec<Option<u32>> = solutions
+                .iter()
+                .map(|solution| solution.compute(line, replace))
+                .collect();
+            if values.iter().any(|value| *value != expected) {
+                diffs.push(LineDiff {
+                    line: i + 1,
+                    text: line,
+                    expected,
+                    values,
+                });
+            }
+        }
+        return (names, diffs);
+    }
+}
+
+impl Default for Trebuchet {
+    fn default() -> Trebuchet {
+        return Trebuchet::with_methods(line_sum_array, None, false);
+    }
+}
+
+impl Solution for Trebuchet {
+    type Input<'a> = &'a str;
+
+    /*
+     * The calibration document is kept as is, and its lines are borrowed from it as they are
+     * summed, so parsing allocates nothing however large the input. Non-ASCII lines are only
+     * looked for when the document is not ASCII.
+     */
+    fn parse<'a>(&self, input: &'a str) -> Result<&'a str> {
+        if input.is_ascii() || self.vocab.unicode() {
+            return Ok(input);
+        }
+        for (i, line) in input.lines().enumerate() {
+            if !line.is_ascii() {
+                eprintln!(
+                    "Warning: line {} is not ASCII; use --unicode to read its digits and words: {}",
+                    i + 1,
+                    line
+                );
+            }
+        }
+        return Ok(input);
+    }
+
+    /* Every combination of summation method and word replacement strategy. */
+    fn variants(&self) -> Vec<(String, Box<dyn Runner>)> {
+        return self
+            .combinations()
+            .into_iter()
+            .map(|(name, solution)| (name, Box::new(solution) as Box<dyn Runner>))
+            .collect();
+    }
+
+    /* Part one sums digits only, part two also spelled-out digits. */
+    fn accumulator(&self, part: Part) -> Option<Box<dyn Accumulator>> {
+        let replace: bool = part == Part::Two;
+        return Some(match self.total {
+            Width::U64 => Box::new(Calibration::<u64>::new(self.clone(), replace)),
+            Width::U128 => Box::new(Calibration::<u128>::new(self.clone(), replace)),
+            #[cfg(feature = "bigint")]
+            Width::Big => Box::new(Calibration::<BigUint>::new(self.clone(), replace)),
+        });
+    }
+
+    /* Sum of the first and last digit of each line. */
+    fn part_one(&self, input: &&str) -> Result<Answer> {
+        let (answer, _): (Answer, Tally) = self.calibrate(input, false)?;
+        return Ok(answer);
+    }
+
+    /* Sum of the first and last digit or spelled-out digit of each line. */
+    fn part_two(&self, input: &&str) -> Result<Answer> {
+        let (answer, _): (Answer, Tally) = self.calibrate(input, true)?;
+        return Ok(answer);
+    }
+}
+
+impl<T: Total> Calibration<T> {
+    /* Empty running sum of one part. */
+    fn new(solution: Trebuchet, replace: bool) -> Calibration<T> {
+        return Calibration {
+            solution,
+            replace,
+            lines: 0,
+            sum: T::zero(),
+            tally: Tally::default(),
+        };
+    }
+}
+
+impl<T: Total> Accumulator for Calibration<T> {
+    /* Add the calibration value of the next line. */
+    fn push_line(&mut self, line: &str) -> Result<()> {
+        self.lines += 1;
+        self.sum = self.solution.add_line(
+            self.sum.clone(),
+            &mut self.tally,
+            self.lines,
+            line,
+            self.replace,
+        )?;
+        return Ok(());
+    }
+
+    fn current(&self) -> Answer {
+        return self.sum.answer(&self.solution.vocab);
+    }
+}
+
+impl Width {
+    /* Integer type named as in `TOTALS`. */
+    pub fn from_name(name: &str) -> Option<Width> {
+        return match name {
+            "u64" => Some(Width::U64),
+            "u128" => Some(Width::U128),
+            #[cfg(feature = "bigint")]
+            "big" => Some(Width::Big),
+            _ => None,
+        };
+    }
+}
+
+impl OnMissing {
+    /* Policy named as in `MISSING_POLICIES`. */
+    pub fn from_name(name: &str) -> Option<OnMissing> {
+        return match name {
+            "error" => Some(OnMissing::Error),
+            "skip" => Some(OnMissing::Skip),
+            "zero" => Some(OnMissing::Zero),
+            _ => None,
+        };
+    }
+}
+
+impl OnEmpty {
+    /* Policy named as in `EMPTY_POLICIES`. */
+    pub fn from_name(name: &str) -> Option<OnEmpty> {
+        return match name {
+            "error" => Some(OnEmpty::Error),
+            "skip" => Some(OnEmpty::Skip),
+            _ => None,
+        };
+    }
+}
+
+impl Tally {
+    /* Add the counts of another chunk of lines. */
+    fn add(&mut self, other: Tally) {
+        self.missing += other.missing;
+        self.empty += other.empty;
+    }
+}
+
+/* Count of lines with a noun, such as "1 empty line" or "2 empty lines". */
+fn count_lines(n: usize, noun: &str) -> String {
+    return format!("{} {}{}", n, noun, if n == 1 { "" } else { "s" });
+}
+
+impl Total for u64 {
+    const NAME: &'static str = "u64";
+
+    fn zero() -> u64 {
+        return 0;
+    }
+
+    fn from_value(value: u32) -> u64 {
+        return u64::from(value);
+    }
+
+    fn checked_add(&self, other: &u64) -> Option<u64> {
+        return u64::checked_add(*self, *other);
+    }
+
+    fn answer(&self, vocab: &Vocab) -> Answer {
+        if vocab.radix() == 10 {
+            return Answer::from(*self);
+        }
+        return Answer::Text(vocab.format(*self));
+    }
+}
+
+impl Total for u128 {
+    const NAME: &'static str = "u128";
+
+    fn zero() -> u128 {
+        return 0;
+    }
+
+    fn from_value(value: u32) -> u128 {
+        return u128::from(value);
+    }
+
+    fn checked_add(&self, other: &u128) -> Option<u128> {
+        return u128::checked_add(*self, *other);
+    }
+
+    fn answer(&self, vocab: &Vocab) -> Answer {
+        if vocab.radix() == 10 {
+            return Answer::from(*self);
+        }
+        return Answer::Text(vocab.format(*self));
+    }
+}
+
+#[cfg(feature = "bigint")]
+impl Total for BigUint {
+    const NAME: &'static str = "big";
+
+    fn zero() -> BigUint {
+        return BigUint::ZERO;
+    }
+
+    fn from_value(value: u32) -> BigUint {
+        return BigUint::from(value);
+    }
+
+    /* Never overflows. */
+    fn checked_add(&self, other: &BigUint) -> Option<BigUint> {
+        return Some(self + other);
+    }
+
+    fn answer(&self, vocab: &Vocab) -> Answer {
+        if let Ok(n) = i64::try_from(self) {
+            if vocab.radix() == 10 {
+                return Answer::Int(n);
+            }
+        }
+        return Answer::Text(self.to_str_radix(vocab.radix()));
+    }
+}
+
+///
+/// Tests
+///
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /* Digit words, digit chars, Roman numerals, and Unicode chars that lines are built from. */
+    static TOKENS: [&str; 35] = [
+        "", "one", "two", "three", "eight", "nine", "Two", "sIX", "NINE", "twone", "eightwo",
+        "oneight", "7", "0", "a", "f", "z", "I", "V", "X", "IX", "IV", "VII", "XVI", "dos", "tres",
+        "VIER", "neun", "acht", "xx", "İ", "\u{212A}", "ß", "٣", "１",
+    ];
+
+    /* Every line of one or two tokens, including the empty line. */
+    fn token_lines() -> Vec<String> {
+        let mut lines: Vec<String> = Vec::new();
+        for a in TOKENS {
+            for b in TOKENS {
+                lines.push(format!("{}{}", a, b));
+            }
+        }
+        return lines;
+    }
+
+    /* Assert that every combination of methods computes the reference value of every line. */
+    fn assert_combinations_match_reference(vocab: Vocab) {
+        let solution: Trebuchet = Trebuchet::default().with_vocab(vocab.clone());
+        let lines: Vec<String> = token_lines();
+        for (replace, mode) in [(false, Mode::Digits), (true, Mode::Words)] {
+            for (name, method) in solution.combinations() {
+                for line in &lines {
+                    assert_eq!(
+                        method.compute(line, replace),
+                        vocab.calibration_value(line, mode),
+                        "{} on {:?}, part {}",
+                        name,
+                        line,
+                        if replace { 2 } else { 1 }
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn reference_resolves_overlapping_words() {
+        assert_eq!(calibration_value("eightwo", Mode::Words), Some(82));
+        assert_eq!(calibration_value("twone", Mode::Words), Some(21));
+        assert_eq!(calibration_value("oneight", Mode::Words), Some(18));
+        assert_eq!(calibration_value("eightwo", Mode::Digits), None);
+        assert_eq!(calibration_value("", Mode::Words), None);
+    }
+
+    #[test]
+    fn reference_matches_readme_examples() {
+        let part_one: [(&str, u32); 4] = [
+            ("1abc2", 12),
+            ("pqr3stu8vwx", 38),
+            ("a1b2c3d4e5f", 15),
+            ("treb7uchet", 77),
+        ];
+        for (line, value) in part_one {
+            assert_eq!(
+                calibration_value(line, Mode::Digits),
+                Some(value),
+                "{}",
+                line
+            );
+        }
+        let part_two: [(&str, u32); 7] = [
+            ("two1nine", 29),
+            ("eightwothree", 83),
+            ("abcone2threexyz", 13),
+            ("xtwone3four", 24),
+            ("4nineeightseven2", 42),
+            ("zoneight234", 14),
+            ("7pqrstsixteen", 76),
+        ];
+        for (line, value) in part_two {
+            assert_eq!(
+                calibration_value(line, Mode::Words),
+                Some(value),
+                "{}",
+                line
+            );
+        }
+    }
+
+    #[test]
+    fn reference_reads_radix_roman_and_unicode() {
+        let hex: Vocab = Vocab::english().with_radix(16);
+        assert_eq!(hex.calibration_value("xa1fx", Mode::Digits), Some(0xaf));
+        assert_eq!(hex.format(0xafu32), "af");
+        let roman: Vocab = Vocab::english().with_roman(true);
+        assert_eq!(roman.calibration_value("xIXyIVz", Mode::Words), Some(94));
+        assert_eq!(roman.calibration_value("IIII", Mode::Words), None);
+        // in radix 36 the numeral letters are digits, whichever builder is called first
+        for vocab in [
+            Vocab::english().with_roman(true).with_radix(36),
+            Vocab::english().with_radix(36).with_roman(true),
+        ] {
+            assert_eq!(
+                vocab.calibration_value("XI", Mode::Words),
+                Some(33 * 36 + 18)
+            );
+            let solution: Trebuchet = Trebuchet::default().with_vocab(vocab);
+            assert_eq!(solution.compute("XI", true), Some(33 * 36 + 18));
+        }
+        let unicode: Vocab = Vocab::english().with_unicode(true);
+        assert_eq!(unicode.calibration_value("١TWOne", Mode::Words), Some(11));
+        assert_eq!(unicode.calibration_value("TWOne", Mode::Words), Some(21));
+        // "İ" lowercases to "i" and a combining dot, which is not a digit even in radix 36
+        let radix36: Vocab = Vocab::english().with_radix(36).with_unicode(true);
+        assert_eq!(
+            radix36.calibration_value("İx", Mode::Words),
+            Some(33 * 36 + 33)
+        );
+        let both: Vocab = Vocab::english().with_roman(true).with_unicode(true);
+        let spanish: Vocab = Vocab::parse(include_str!("vocab/es.txt")).expect("es.txt parses");
+        let spanish: Vocab = spanish.with_roman(true).with_unicode(true);
+        assert_eq!(both.calibration_value("sIXtwo", Mode::Words), Some(62));
+        assert_eq!(spanish.calibration_value("dosIXtwo", Mode::Words), Some(29));
+    }
+
+    #[test]
+    fn digit_table_holds_every_word() {
+        for (i, word) in WORDS.iter().enumerate() {
+            assert_eq!(DIGIT_TABLE.get(word.as_bytes()), Some(i as u32 + 1));
+        }
+        assert_eq!(DIGIT_TABLE.get(b"zero"), None);
+        assert_eq!(DIGIT_TABLE.get(b"eigh"), None);
+        assert_eq!(DIGIT_TABLE.get(b""), None);
+    }
+
+    #[test]
+    fn combinations_match_reference_in_decimal() {
+        assert_combinations_match_reference(Vocab::english());
+    }
+
+    #[test]
+    fn combinations_match_reference_in_large_radixes() {
+        for radix in [16, 19, 25, 30, 36] {
+            assert_combinations_match_reference(Vocab::english().with_radix(radix));
+            let unicode: Vocab = Vocab::english().with_radix(radix).with_unicode(true);
+            assert_combinations_match_reference(unicode);
+        }
+    }
+
+    #[test]
+    fn combinations_match_reference_with_roman_and_unicode() {
+        let spanish: Vocab = Vocab::parse(include_str!("vocab/es.txt")).expect("es.txt parses");
+        let german: Vocab = Vocab::parse(include_str!("vocab/de.txt")).expect("de.txt parses");
+        for radix in [10, 17, 18] {
+            for (roman, unicode) in [(true, false), (false, true), (true, true)] {
+                for vocab in [Vocab::english(), spanish.clone(), german.clone()] {
+                    let vocab: Vocab = vocab
+                        .with_radix(radix)
+                        .with_roman(roman)
+                        .with_unicode(unicode);
+                    assert_combinations_match_reference(vocab);
+                }
+            }
+        }
+    }
+
+    /* Line number an overflow error is reported at, or `None` for any other result. */
+    fn overflow_line<T>(result: Result<T>) -> Option<usize> {
+        return match result {
+            Err(Error::Overflow { location, .. }) => location.line,
+            _ => None,
+        };
+    }
+
+    #[test]
+    fn overflow_names_its_line_serially_and_threaded() {
+        // ten lines of 99; starting 6 * 99 + 50 below the maximum, line 7 overflows
+        let text: String = "a9b\n".repeat(10);
+        let start: u64 = u64::MAX - 6 * 99 - 50;
+        let solution: Trebuchet = Trebuchet::default();
+        let (sum, _): (u64, Tally) = solution
+            .sum_chunk(&text[..4 * 6], 1, start, false)
+            .expect("six lines fit");
+        assert_eq!(sum, u64::MAX - 50);
+        assert_eq!(
+            overflow_line(solution.sum_chunk(&text, 1, start, false)),
+            Some(7)
+        );
+        // chunks of every size, so the overflow falls at the start, middle, and end of a chunk
+        for threads in 2..=10 {
+            let solution: Trebuchet = Trebuchet::default().threaded(threads);
+            assert_eq!(
+                overflow_line(solution.sum_lines_parallel(&text, start, false)),
+                Some(7),
+                "{} threads",
+                threads
+            );
+        }
+        // the first line reaches the maximum exactly, so the second one overflows
+        let big: Trebuchet = Trebuchet::default().threaded(2);
+        assert_eq!(
+            overflow_line(big.sum_lines_parallel(&text, u64::MAX - 99, false)),
+            Some(2)
+        );
+    }
+
+    #[test]
+    fn unknown_method_names_are_usage_errors() {
+        assert!(matches!(
+            Trebuchet::new("bogus", "array", false),
+            Err(Error::Usage(_))
+        ));
+        assert!(matches!(
+            Trebuchet::new("array", "bogus", false),
+            Err(Error::Usage(_))
+        ));
+    }
+
+    #[test]
+    fn policies_skip_or_zero_lines_and_count_them() {
+        let text: &str = "a1b2\n\nxyz\nthree4\n";
+        let strict: Trebuchet = Trebuchet::default();
+        assert!(matches!(
+            strict.calibrate(text, false),
+            Err(Error::Parse { .. })
+        ));
+        let lenient: Trebuchet = Trebuchet::default()
+            .with_policies(OnMissing::Zero, OnEmpty::Skip)
+            .threaded(3);
+        let (answer, tally): (Answer, Tally) =
+            lenient.calibrate(text, true).expect("policies apply");
+        assert_eq!(answer, Answer::Int(46));
+        assert_eq!(
+            tally,
+            Tally {
+                missing: 1,
+                empty: 1
+            }
+        );
+    }
+}
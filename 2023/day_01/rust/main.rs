@@ -1,6 +1,6 @@
 //!
 //! FILENAME
-//!   AdventOfCode/2023/day_01_trubuchet/main.rs
+//!   AdventOfCode/2023/day_01/rust/main.rs
 //!
 //! DESCRIPTION
 //!   December 01: The Trebuchet Problem
@@ -12,602 +12,358 @@
 //!   2023-12-01
 //!
 //! NOTES
-//!   -
+//!   Command-line entry point. The solution itself lives in `lib.rs`.
 //!
 
-#![allow(dead_code)]
-
-use std::collections::HashMap;
-use std::env;
-use std::fs::File;
-use std::io::Result as IoResult;
-use std::io::{BufRead, BufReader, Lines};
 use std::path::{Path, PathBuf};
 
-/*
- * Crates
- *
- * Aho-Corasick algorithm for time- and space-optimal string replacement.
- * Time complexity: O(n + m + z) where:
- *   n = string length
- *   m = number of characters in all words
- *   z = number of word occurrences
- * Intuitively, the algorithm works by constructing a fully connected trie of all search words
- * before walking the trie for each character in the string.
- */
-use aho_corasick::AhoCorasick;
+use aoc_common::cli::{self, Matches, Opt, Spec};
+use aoc_common::{
+    banner, colorize, problem, Answer, Buffer, Error, Part, Result, Solution, Source,
+};
+use day_01_trebuchet::{
+    LineDiff, OnEmpty, OnMissing, Tally, Trebuchet, Vocab, Width, EMPTY_POLICIES, MISSING_POLICIES,
+    TOTALS, WORDS_METHODS,
+};
 
 ///
 /// Variables, Data Structures, Types
 ///
 
+/* Directory of this day's puzzle files, wherever the binary is run from. */
+const DAY_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/..");
+
+/* Summation methods, and `all` to compare every combination of methods with `--diff`. */
+static SUM_CHOICES: [&str; 5] = ["array", "iterator", "simd", "trie", "all"];
+
 /* CLI arguments */
 struct CliArgs {
-    input: PathBuf,
+    input: Source,
     method_sum: String,
-    replace: bool,
+    method_replace: String,
+    vocab: Option<PathBuf>,
+    radix: u32,
+    roman: bool,
+    unicode: bool,
+    part: Part,
+    check: bool,
+    threads: usize,
+    total: Width,
+    on_missing: OnMissing,
+    on_empty: OnEmpty,
+    diff: bool,
     verbose: bool,
 }
 type Args = CliArgs;
 
-/*
- * Digit string-to-byte key-value store.
- */
-static WORDS: [&str; 9] = [
-    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
-];
-static DIGITS: [&str; 9] = ["1", "2", "3", "4", "5", "6", "7", "8", "9"];
-static INTS: [u8; 9] = [49, 50, 51, 52, 53, 54, 55, 56, 57]; // ASCII/UTF-8 byte encoding
+/* Command-line options. The `--help` text is generated from this spec. */
+static SPEC: Spec = Spec {
+    name: "day_01_trebuchet",
+    about: "Return the solution to Advent of Code 2023-12-01: Trebuchet.",
+    args: &[],
+    options: &[
+        cli::INPUT,
+        cli::TEXT,
+        cli::EXAMPLE,
+        Opt::value(Some('s'), "sum", "NAME", "Line summation method.")
+            .choices(&SUM_CHOICES)
+            .default("array"),
+        Opt::value(Some('w'), "words", "NAME", "Word replacement method.")
+            .choices(&WORDS_METHODS)
+            .default("array"),
+        Opt::value(
+            None,
+            "vocab",
+            "FILE",
+            "Word-to-digit table of `WORD VALUE` lines. Defaults to English.",
+        ),
+        Opt::value(
+            None,
+            "radix",
+            "N",
+            "Digit base, 2 to 36. Letters a-z are the digits 10 to 35.",
+        )
+        .default("10"),
+        Opt::flag(
+            None,
+            "roman",
+            "Also read Roman numerals below the radix as digits in part 2.",
+        ),
+        Opt::flag(
+            Some('u'),
+            "unicode",
+            "Read Unicode decimal digits, and match words ignoring case.",
+        ),
+        cli::PART,
+        Opt::flag(
+            Some('r'),
+            "replace",
+            "Replace words with digits. Same as `--part 2`.",
+        ),
+        Opt::flag(
+            Some('c'),
+            "check",
+            "Check each line against the reference `calibration_value`.",
+        ),
+        Opt::value(
+            Some('j'),
+            "threads",
+            "N",
+            "Sum lines in N threads. 0 uses one per CPU.",
+        )
+        .default("1"),
+        Opt::value(
+            None,
+            "total",
+            "TYPE",
+            "Integer type of the total. `big`, of any size, needs the `bigint` feature.",
+        )
+        .choices(&TOTALS)
+        .default("u64"),
+        Opt::value(
+            None,
+            "on-missing",
+            "POLICY",
+            "What to do with a line without a digit: fail, skip it, or count it as 0.",
+        )
+        .choices(&MISSING_POLICIES)
+        .default("error"),
+        Opt::value(
+            None,
+            "on-empty",
+            "POLICY",
+            "What to do with an empty line: fail or skip it.",
+        )
+        .choices(&EMPTY_POLICIES)
+        .default("error"),
+        Opt::flag(
+            None,
+            "diff",
+            "With `--sum all`, print the lines on which any two combinations of methods disagree.",
+        ),
+        cli::VERBOSE,
+        cli::PROBLEM,
+        cli::HELP,
+    ],
+    examples: &[
+        "./day_01_trebuchet --problem",
+        "./day_01_trebuchet --sum array --replace",
+        "./day_01_trebuchet --sum iterator --words builtin --replace --check",
+        "./day_01_trebuchet --input entrada.txt --vocab vocab/es.txt --replace",
+        "./day_01_trebuchet --input hex.txt --radix 16",
+        "./day_01_trebuchet --input ../data/input.txt --sum iterator --words trie --part 2",
+        "./day_01_trebuchet --input generated.txt --replace --threads 8 --total u128",
+        "./day_01_trebuchet --input notes.txt --on-missing skip --on-empty skip",
+        "./day_01_trebuchet --sum all --diff --replace",
+        "./day_01_trebuchet --example 2 --replace",
+        "./day_01_trebuchet --text two1nine --replace",
+        "cat ../input.txt | ./day_01_trebuchet --input -",
+        "./day_01_trebuchet --help",
+    ],
+};
 
 ///
 /// Functions
 ///
 
 /*
- * Zip two string arrays into a hashmap.
+ * Parse command-line arguments.
  */
-fn generate_digits_hashmap() -> HashMap<String, u8> {
-    let mut map: HashMap<String, u8> = HashMap::new();
-    for (k, v) in WORDS.into_iter().zip(INTS.into_iter()) {
-        map.insert(String::from(k), v);
+fn parse_args() -> Args {
+    let matches: Matches = SPEC.parse_env();
+    if matches.flag("problem") {
+        if let Err(e) = problem(Path::new(DAY_DIR)) {
+            e.exit();
+        }
+        std::process::exit(0);
     }
-    return map;
-}
-
-/*
- * Get digit char for string. Approximate HashMap.
- */
-fn get_digit_char(word: &str) -> char {
-    let digit: char = match word {
-        "one" => '1',
-        "two" => '2',
-        "three" => '3',
-        "four" => '4',
-        "five" => '5',
-        "six" => '6',
-        "seven" => '7',
-        "eight" => '8',
-        "nine" => '9',
-        &_ => ' ', // default if no match found
-    };
-    return digit;
-}
-
-/*
- * Get digit byte for byte-array. Approximate HashMap.
- */
-fn get_digit_u8(word: &[u8]) -> u8 {
-    let digit: u8 = match word {
-        [b'z', b'e', b'r', b'o'] => 0,
-        [b'o', b'n', b'e'] => 1,
-        [b't', b'w', b'o'] => 2,
-        [b't', b'h', b'r', b'e', b'e'] => 3,
-        [b'f', b'o', b'u', b'r'] => 4,
-        [b'f', b'i', b'v', b'e'] => 5,
-        [b's', b'i', b'x'] => 6,
-        [b's', b'e', b'v', b'e', b'n'] => 7,
-        [b'e', b'i', b'g', b'h', b't'] => 8,
-        [b'n', b'i', b'n', b'e'] => 9,
-        _ => 0, // default value if no match found
+    // `--replace` is kept for parity with the Python, Julia, and C++ solutions
+    let part: Part = if matches.flag("replace") {
+        Part::Two
+    } else {
+        matches.part()
     };
-    return digit;
-}
-
-/*
- * Colorize strings using ANSI escape sequences.
- * -------------------------------------------------------------------
- * | Color   | Text | Background | Bright Text  |  Bright Background |
- * -------------------------------------------------------------------
- * | Black   |  30  | 40         | 90           | 100                |
- * | Red     |  31  | 41         | 91           | 101                |
- * | Green   |  32  | 42         | 92           | 102                |
- * | Yellow  |  33  | 43         | 93           | 103                |
- * | Blue    |  34  | 44         | 94           | 104                |
- * | Magenta |  35  | 45         | 95           | 105                |
- * | Cyan    |  36  | 46         | 96           | 106                |
- * | White   |  37  | 47         | 97           | 107                |
- * | Default |  39  | 49         | 99           | 109                |
- * -------------------------------------------------------------------
- */
-fn colorize(text: &str, color: &str, bright: bool, back: bool) -> String {
-    let mut code: u8 = match color {
-        "black" => 30,
-        "red" => 31,
-        "green" => 32,
-        "yellow" => 33,
-        "blue" => 34,
-        "magenta" => 35,
-        "cyan" => 36,
-        "white" => 37,
-        "default" => 39,
-        &_ => 39, // set to default if no match found
+    let input: Source = match Source::from_matches(&matches, Path::new(DAY_DIR)) {
+        Ok(source) => source,
+        Err(e) => Error::from(e).exit(),
     };
-    if back {
-        code += 10;
-    }
-    if bright {
-        code += 60;
-    }
-    return format!("\x1b[{}m{}\x1b[0m", code, text);
-}
-
-/*
- * Encode u8 to char (ASCII/UTF-8).
- */
-fn u8_to_char(bytes: u8) -> char {
-    let d: u32 = u32::from(bytes);
-    let c: char = char::from_u32(d).unwrap();
-    return c;
-}
-
-/*
- * Decode char (ASCII) to u8.
- */
-fn char_to_u8(c: char) -> u8 {
-    let u: u8 = c as u8;
-    return u - 48; // same as char - '0'
-}
-
-/*
- * Decode char (ASCII/UTF-8) to u32.
- */
-fn char_to_u32(c: char) -> u32 {
-    let u: u32 = u32::from(c);
-    return u - 48; // same as char - '0'
-}
-
-/*
- * Check if byte array is valid ASCII/UTF-8 codes.
- */
-fn is_utf8_bytes(data: &[u8]) -> bool {
-    match std::str::from_utf8(&data[0..std::cmp::min(data.len(), 4)]) {
-        Ok(s) => s.chars().next().is_some(),
-        Err(e) => e.valid_up_to() > 0,
-    }
-}
-
-/*
- * Determine whether byte is a valid ASCII digit in the range 48-57.
- */
-fn is_utf8_byte(byte: u8) -> bool {
-    return if byte > 47 && byte < 58 { true } else { false };
-}
-
-/* Concatenate a pair of integers. */
-fn concat_u32_pair(left: u32, right: u32) -> u32 {
-    return (left * 10) + right;
-}
-
-/* Concatenate an array of integers. */
-fn concat_u32_array(ints: &[u32]) -> u32 {
-    return ints
-        .iter()
-        .enumerate()
-        .map(|(i, x)| x * (u32::pow(10, (ints.len() - i) as u32)))
-        .sum();
-}
-
-/*
- * Read lines from a file path. Output is wrapped in a Result to allow matching on errors.
- * Returns an Iterator to the Reader of the lines of the file.
- */
-fn read_lines<P>(filename: P) -> IoResult<Lines<BufReader<File>>>
-where
-    P: AsRef<Path>,
-{
-    let file = File::open(filename)?;
-    Ok(BufReader::new(file).lines())
-}
-
-/*
- * Find and replace words with digits from the zeroth index. The outer function increments the index.
- * If char byte is start of digit string bytes, convert to decimal char and increment index by string length.
- */
-fn words_to_digits_array(line_bytes: &mut Vec<u8>, index: usize, verbose: bool) -> () {
-    // variables
-    let mut word_bytes: &[u8]; // word byte array
-    let mut word_len: usize; // word length
-    let mut substring: &[u8]; // subarray from index to word end
-    let mut end: usize; // end index
-    for (i, word) in WORDS.iter().enumerate() {
-        // loop over each key-value pair and replace words with digits. WORDS and DIGITS are equal length.
-        word_bytes = word.as_bytes();
-        word_len = word_bytes.len();
-        end = index + word_len - 1;
-        // bounds check
-        if end > line_bytes.len() - 1 {
-            continue;
+    let radix: String = String::from(matches.value("radix").unwrap_or_default());
+    let radix: u32 = match radix.parse::<u32>() {
+        Ok(r) if (2..=36).contains(&r) => r,
+        _ => {
+            let message: String =
+                format!("invalid value {:?} for --radix (expected 2 to 36)", radix);
+            Error::Usage(message).exit();
         }
-        substring = &line_bytes[index..=end]; // slice from the starting index to n-char
-                                              //if verbose {
-                                              //    println!(
-                                              //        "string: {}, index: {}, end: {}, length: {}, substring: {}, word: {}",
-                                              //        std::str::from_utf8(&line_bytes).unwrap(),
-                                              //        index,
-                                              //        end,
-                                              //        word_len,
-                                              //        std::str::from_utf8(&substring).unwrap(),
-                                              //        word
-                                              //    );
-                                              //}
-        if substring == word_bytes {
-            line_bytes[index] = INTS[i];
-            if verbose {
-                println!("updated: {}", std::str::from_utf8(&line_bytes).unwrap());
-            }
-            break;
+    };
+    let threads: String = String::from(matches.value("threads").unwrap_or_default());
+    let threads: usize = match threads.parse::<usize>() {
+        Ok(n) => n,
+        Err(_) => {
+            let message: String = format!(
+                "invalid value {:?} for --threads (expected a number)",
+                threads
+            );
+            Error::Usage(message).exit();
         }
+    };
+    // `--sum all` only makes sense when comparing methods, and comparing needs every method
+    let method_sum: String = String::from(matches.value("sum").unwrap_or_default());
+    let diff: bool = matches.flag("diff");
+    if diff != (method_sum == "all") {
+        let message: &str = "--sum all and --diff must be used together";
+        Error::Usage(String::from(message)).exit();
     }
-    return ();
-}
-
-/*
- * Find and replace integer words with digits. Idiomatic built-in method.
- */
-fn words_to_digits_builtin(line: String, verbose: bool) -> String {
-    if verbose {
-        println!("line: {}", line);
-    }
-    let mut result: String = String::from("");
-    // loop over each key-value pair and replace words with digits
-    let kv: HashMap<String, u8> = generate_digits_hashmap();
-    for (k, v) in kv {
-        result = line.replace(&k, std::str::from_utf8(&[v]).unwrap());
-    }
-    return result;
-}
-
-/*
- * Find and replace integer words with digits. Aho-Corasick method from crate.
- */
-fn words_to_digits_ac(line: String, verbose: bool) -> String {
-    if verbose {
-        println!("line: {}", line);
-    }
-    let mut result: Vec<u8> = vec![];
-    let ac: AhoCorasick = AhoCorasick::new(WORDS).unwrap();
-    ac.try_stream_replace_all(line.as_bytes(), &mut result, &[INTS])
-        .unwrap();
-    return String::from_utf8_lossy(&result).to_string();
+    return CliArgs {
+        input,
+        method_sum,
+        method_replace: String::from(matches.value("words").unwrap_or_default()),
+        vocab: matches.value("vocab").map(PathBuf::from),
+        radix,
+        roman: matches.flag("roman"),
+        unicode: matches.flag("unicode"),
+        part,
+        check: matches.flag("check"),
+        threads,
+        total: Width::from_name(matches.value("total").unwrap_or_default()).unwrap_or(Width::U64),
+        on_missing: OnMissing::from_name(matches.value("on-missing").unwrap_or_default())
+            .unwrap_or(OnMissing::Error),
+        on_empty: OnEmpty::from_name(matches.value("on-empty").unwrap_or_default())
+            .unwrap_or(OnEmpty::Error),
+        diff,
+        verbose: matches.flag("verbose"),
+    };
 }
 
 /*
- * Loop over line string (char array) and return the sum of outermost digits.
- * We do not use mutability on left or right, but the compiler complains anyway.
- * Convert string to bytes and loop over indices (the C way) not iterators (the Rust way).
+ * Print the lines on which the combinations of methods disagree as a table, with the reference
+ * value and each combination's value. Values other than the reference are highlighted.
  */
-#[allow(unused_mut)]
-fn line_sum_array(line: String, replace: bool, verbose: bool) -> u32 {
-    // variables
-    let n: usize; // line string length
-    let mut c: char; // char
-    let mut left: u32; // left digit value
-    let mut right: u32; // right digit value
-    let mut sum: u32 = 0; // sum of first and last digit chars
-    let mut line_bytes: Vec<u8> = Vec::from(line.as_bytes());
-    if verbose {
-        println!("line: {}", std::str::from_utf8(&line_bytes).unwrap());
-    }
-    // loop over the byte array and collect digits of radix 10 (ASCII numbers)
-    n = line_bytes.len();
-    let mut j: usize = 0;
-    let mut k: usize = n - 1;
-    while j < n {
-        // check if j is start of digit word; if true, update line_bytes[j] and j in-place
-        if replace {
-            words_to_digits_array(&mut line_bytes, j, verbose);
-        }
-        //if verbose { println!("j: {}", j); }
-        c = u8_to_char(line_bytes[j]);
-        //if verbose { println!("c: {}", c); }
-        if c.is_digit(10) {
-            left = char_to_u32(c);
-            if verbose {
-                println!("{}: {}", "left", c);
-            }
-            while k >= j {
-                // check if k is start of digit word; if true, update line_bytes[k] and k in-place
-                if replace {
-                    words_to_digits_array(&mut line_bytes, k, verbose);
-                }
-                //println!("k: {}", k);
-                c = u8_to_char(line_bytes[k]);
-                //println!("c: {}", c);
-                if c.is_digit(10) {
-                    right = char_to_u32(c);
-                    if verbose {
-                        println!("{}: {}", "right", c);
-                    }
-                    sum = (left * 10) + right;
-                    if verbose {
-                        println!("{}{} = {}", left, right, sum);
-                    }
-                    return sum;
-                }
-                k -= 1;
-            }
+fn print_diff(names: &[String], diffs: &[LineDiff], vocab: &Vocab, lines: usize) {
+    let cell = |value: Option<u32>| -> String {
+        return value.map_or(String::from("-"), |v| vocab.format(v));
+    };
+    let mut headers: Vec<&str> = vec!["line", "reference"];
+    headers.extend(names.iter().map(String::as_str));
+    let mut widths: Vec<usize> = headers.iter().map(|h| h.len()).collect();
+    for diff in diffs {
+        widths[0] = widths[0].max(diff.line.to_string().len());
+        widths[1] = widths[1].max(cell(diff.expected).len());
+        for (i, value) in diff.values.iter().enumerate() {
+            widths[i + 2] = widths[i + 2].max(cell(*value).len());
         }
-        j += 1;
     }
-    return sum;
-}
-
-/*
- * Idiomatic Rusty variant of `line_sum` function. For comparison of readability and performance.
- */
-#[allow(unused_assignments)]
-fn line_sum_iterator(mut line: String, replace: bool, verbose: bool) -> u32 {
-    // variables
-    let mut ch: Option<(usize, char)>;
-    let mut left: u32 = 0;
-    let mut right: u32 = 0;
-    let mut sum: u32 = 0; // sum of first and last digit chars
-    if verbose {
-        println!("line: {}", line);
+    if !diffs.is_empty() {
+        let mut row: Vec<String> = headers
+            .iter()
+            .zip(&widths)
+            .map(|(h, w)| format!("{:>w$}", h, w = w))
+            .collect();
+        row.push(String::from("text"));
+        println!("  {}", row.join("  "));
     }
-    // loop over chars from left and then from right if digit is found
-    loop {
-        ch = line.char_indices().next(); // pop value from left
-        if ch.is_none() {
-            break;
-        }
-        if replace {
-            let j: usize = ch.unwrap().0;
-            for (i, word) in WORDS.iter().enumerate() {
-                let n: usize = word.len();
-                if (line.char_indices().last().unwrap().0 < j + n) && (&&line[j..j + n] == word) {
-                    line.replace_range(j..j + 1, DIGITS[i]);
-                    break;
-                }
-            }
-        }
-        if ch.unwrap().1.is_digit(10) {
-            // if character is a valid ASCII/UTF-8 digit
-            left = char_to_u32(ch.unwrap().1);
-            if verbose {
-                println!("left: {}", left);
-            }
-            loop {
-                ch = line.char_indices().next_back(); // pop value from right
-                if ch.is_none() {
-                    break;
-                }
-                if replace {
-                    let k: usize = ch.unwrap().0;
-                    for (i, word) in WORDS.iter().enumerate() {
-                        let n: usize = word.len();
-                        if (line.char_indices().last().unwrap().0 < k + n)
-                            && (&&line[k..k + n] == word)
-                        {
-                            line.replace_range(k..k + 1, DIGITS[i]);
-                            break;
-                        }
-                    }
-                }
-                if ch.unwrap().1.is_digit(10) {
-                    right = char_to_u32(ch.unwrap().1);
-                    if verbose {
-                        println!("right: {}", right);
-                    }
-                    sum = left + right;
-                    if verbose {
-                        println!("{} + {} = {}", left, right, sum);
-                    }
-                    return sum;
-                }
+    for diff in diffs {
+        let mut row: Vec<String> = vec![
+            format!("{:>w$}", diff.line, w = widths[0]),
+            format!("{:>w$}", cell(diff.expected), w = widths[1]),
+        ];
+        for (i, value) in diff.values.iter().enumerate() {
+            let text: String = format!("{:>w$}", cell(*value), w = widths[i + 2]);
+            if *value == diff.expected {
+                row.push(text);
+            } else {
+                row.push(colorize(&text, "red", true, false));
             }
         }
+        row.push(String::from(diff.text));
+        println!("  {}", row.join("  "));
     }
-    if right == 0 {
-        sum = (left * 10) + left;
-        if verbose {
-            println!("{} + {} = {}", left, left, sum);
-        }
+    if diffs.is_empty() {
+        println!(
+            "All {} combinations of methods agree on {} lines",
+            names.len(),
+            lines
+        );
     } else {
-        sum = (left * 10) + right;
-        if verbose {
-            println!("{} + {} = {}", left, right, sum);
-        }
-    }
-    return sum;
-}
-
-/*
- * Higher-order function to parametrically select which line sum method to apply.
- * Options: [ line_sum_array, line_sum_iterator ]
- */
-fn line_sum_fn(f: impl Fn(String, bool, bool) -> u32) -> impl Fn(String, bool, bool) -> u32 {
-    return f; // f returns a function, f() returns a callback
-}
-
-/*
- * Map function name string to function pointer.
- */
-fn line_sum_fn_str(name: &str) -> impl Fn(String, bool, bool) -> u32 {
-    let f: fn(String, bool, bool) -> u32 = match name {
-        "array" => line_sum_array,
-        "iterator" => line_sum_iterator,
-        _ => unimplemented!(),
-    };
-    return f;
-}
-
-/*
- * Print the problem statement.
- */
-fn problem() {
-    let filepath: PathBuf = Path::new("..").join("problem.txt");
-    let statement: String = std::fs::read_to_string(filepath).unwrap().parse().unwrap();
-    println!("\n{}\n", colorize(&statement, "cyan", false, false));
-}
-
-/*
- * Print the banner.
- */
-fn banner() {
-    let filepath: PathBuf = Path::new("..").join("banner.txt");
-    let banner: String = std::fs::read_to_string(filepath).unwrap().parse().unwrap();
-    println!("\n{}\n", colorize(&banner, "cyan", false, false));
-}
-
-/*
- * Print help message.
- */
-fn help() {
-    const HELP: &'static str = r#"
-Usage: main[EXE] [OPTIONS] [--input <PATH>] [--sum <NAME>] [--replace] [--verbose] [--problem] [--help]
-
-Return the solution to Advent of Code 2023-12-01: Trebuchet.
-
-Options:
-  -i, --input <PATH>        Path to the input file. [default: '.\']
-  -s, --sum <NAME>          Line summation method. [options: ["array", "iterator"; default: "array"]
-  -r, --replace             Replace words with digits.
-  -v, --verbose             Print debugging information.
-  -p, --problem             Print problem statement.
-  -h, --help                Print this help message.
-
-Examples:
-  $ /.main.exe --problem
-  $ /.main.exe --sum array --replace
-  $ /.main.exe --input .\data\input.txt --sum array --replace
-  $ /.main.exe --help
-    "#;
-    println!("{}", colorize(HELP, "cyan", false, false));
-}
-
-/*
- * Parse command-line arguments.
- */
-fn parse_args() -> Args {
-    // defaults for variables that we store in CliArgs
-    let mut input: PathBuf = Path::new("..").join("input.txt");
-    let mut method_sum: String = String::from("array");
-    let mut replace: bool = false;
-    let mut verbose: bool = false;
-    // loop over CLI arguments
-    let mut cli_args: std::iter::Skip<env::Args> = env::args().skip(1);
-    while let Some(arg) = cli_args.next() {
-        match &arg[..] {
-            "-i" | "--input" => {
-                if let Some(arg_input) = cli_args.next() {
-                    input = PathBuf::from(arg_input);
-                }
-            }
-            "-s" | "--sum" => {
-                if let Some(arg_method_sum) = cli_args.next() {
-                    method_sum = String::from(arg_method_sum);
-                }
-            }
-            "-r" | "--replace" => {
-                replace = true;
-            }
-            "-v" | "--verbose" => {
-                verbose = true;
-            }
-            "-p" | "--problem" => {
-                problem();
-                std::process::exit(0);
-            }
-            "-h" | "--help" => {
-                help();
-                std::process::exit(0);
-            }
-            _ => {
-                if arg.starts_with('-') {
-                    println!("Unknown argument: {}", arg);
-                } else {
-                    println!("Unknown positional argument: {}", arg);
-                }
-            }
-        }
+        println!(
+            "{} of {} lines disagree across {} combinations of methods",
+            diffs.len(),
+            lines,
+            names.len()
+        );
     }
-    return CliArgs {
-        input,
-        method_sum,
-        replace,
-        verbose,
-    };
 }
 
 ///
 /// Main
 ///
 
-fn main() -> Result<(), &'static str> {
-    // validate variable definitions
-    assert!(WORDS.len() == DIGITS.len());
-    assert!(DIGITS.len() == INTS.len());
+fn main() {
+    if let Err(e) = run() {
+        e.exit();
+    }
+}
 
+/* Solve the selected part, returning typed errors to be reported by `main`. */
+fn run() -> Result<()> {
     // parse command-line arguments
     let args: Args = parse_args();
     if args.verbose {
-        banner();
+        banner(Path::new(DAY_DIR))?;
         println!(
-            "input: {}, method-sum: {}, method-replace: {}",
-            args.input.display(),
-            args.method_sum,
-            args.replace
+            "input: {}, method-sum: {}, method-replace: {}, part: {}",
+            args.input, args.method_sum, args.method_replace, args.part
         );
     }
+    // load the word-to-digit table, English unless `--vocab` is given
+    let vocab: Vocab = match &args.vocab {
+        Some(path) => Vocab::load(path)?,
+        None => Vocab::english(),
+    };
+    let vocab: Vocab = vocab
+        .with_radix(args.radix)
+        .with_roman(args.roman)
+        .with_unicode(args.unicode);
 
-    // variables
-    let mut i: usize; // line counter
-    let mut sum: u32 = 0; // rolling sum
-    let mut line_sum; // line_sum function
-    let mut line_string: String;
-
-    // open `input.txt` file reader buffer
+    // map `input.txt` or another file into memory, or read stdin, inline text, or an example
     if args.verbose {
-        println!("Opening file from path: {}", &args.input.display());
+        println!("Reading input from: {}", args.input);
     }
-    let file = File::open(&args.input).expect("Failed to open file.");
-    let reader = BufReader::new(file);
-
-    // read input file line-by-line and each char from left to right
-    i = 0;
-    for line in reader.lines() {
-        i += 1;
-        if args.verbose {
-            println!(
-                "#: {}",
-                colorize(i.to_string().as_str(), "cyan", true, false)
-            );
-        }
-        line_sum = line_sum_fn_str(&args.method_sum);
-        line_string = String::from(line.unwrap().as_str());
-        if !line_string.is_ascii() {
-            println!("Warning: String contains invalid ASCII: {}", line_string);
-        }
-        sum += line_sum(line_string, args.replace, args.verbose);
-        if args.verbose {
-            println!("{}: {}", "sum", sum);
-        }
+    let buffer: Buffer = args.input.load(Path::new(DAY_DIR))?;
+    let input: &str = buffer.text().map_err(|e| e.with_path(&args.input.name()))?;
+
+    // compare every combination of methods line by line instead of solving
+    if args.diff {
+        let solution: Trebuchet = Trebuchet::default().with_vocab(vocab);
+        let input: &str = solution
+            .parse(input)
+            .map_err(|e| e.with_path(&args.input.name()))?;
+        let (names, diffs): (Vec<String>, Vec<LineDiff>) =
+            solution.diff(input, args.part == Part::Two);
+        print_diff(&names, &diffs, &solution.vocab, input.lines().count());
+        return Ok(());
     }
+
+    let solution: Trebuchet = Trebuchet::new(&args.method_sum, &args.method_replace, args.verbose)?
+        .with_vocab(vocab)
+        .checked(args.check)
+        .threaded(args.threads)
+        .with_total(args.total)
+        .with_policies(args.on_missing, args.on_empty);
+
+    // parse the input once and solve the selected part, counting the lines the policies applied to
+    let (answer, tally): (Answer, Tally) = solution
+        .parse(input)
+        .and_then(|input| solution.calibrate(input, args.part == Part::Two))
+        .map_err(|e| e.with_path(&args.input.name()))?;
     if args.verbose {
-        println!("{}: {}", colorize("total", "red", true, false), sum);
+        println!("{}: {}", colorize("total", "red", true, false), answer);
+    }
+    // report the lines the policies applied to on stderr, keeping stdout to the answer
+    if let Some(summary) = solution.policy_summary(&tally) {
+        eprintln!("Lines: {}", summary);
     }
-    // If no error, return `sum`
-    println!("{}", sum);
+    // If no error, return `answer`
+    println!("{}", answer);
     Ok(())
 }
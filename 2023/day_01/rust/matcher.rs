@@ -0,0 +1,181 @@
+//!
+//! FILENAME
+//!   AdventOfCode/2023/day_01/rust/matcher.rs
+//!
+//! DESCRIPTION
+//!   Purpose-built matcher for the digit words and digit chars of day 1.
+//!
+//! AUTHOR
+//!   Adam Erickson, PhD
+//!
+//! DATE
+//!   2023-12-01
+//!
+//! NOTES
+//!   Only the first and last digit of a line are needed, so instead of reporting every match like
+//!   Aho-Corasick, the patterns are compiled into two byte-level tries: one over the patterns for
+//!   scanning from the left, and one over the reversed patterns for scanning from the right. Each
+//!   trie node is a dense row of 256 transitions, so a step is a single table lookup.
+//!
+
+///
+/// Variables, Data Structures, Types
+///
+
+/* Transition to the root, which no edge leads back to, so it marks a missing edge. */
+const DEAD: u32 = 0;
+
+/* A pattern ending at a trie node: its digit value, and whether it is a word or a digit char. */
+#[derive(Clone, Copy, Debug)]
+struct Terminal {
+    value: u32,
+    word: bool,
+}
+
+/* Byte-level trie in which every node has a dense row of transitions. */
+#[derive(Clone, Debug)]
+struct Trie {
+    next: Vec<[u32; 256]>,
+    terminal: Vec<Option<Terminal>>,
+}
+
+/*
+ * Forward and reversed tries of a vocabulary's words and digit chars. The forward trie finds the
+ * longest pattern starting at an index; the reversed trie finds the shortest pattern ending at one,
+ * which is the one starting furthest to the right.
+ */
+#[derive(Clone, Debug)]
+pub struct Matcher {
+    forward: Trie,
+    reverse: Trie,
+}
+
+///
+/// Functions
+///
+
+impl Trie {
+    /* Empty trie: only the root. */
+    fn new() -> Trie {
+        return Trie {
+            next: vec![[DEAD; 256]],
+            terminal: vec![None],
+        };
+    }
+
+    /* Add a pattern, given as bytes in the order the trie reads them. The first value added wins. */
+    fn insert(&mut self, bytes: impl Iterator<Item = u8>, terminal: Terminal) {
+        let mut node: usize = 0;
+        for b in bytes {
+            if self.next[node][b as usize] == DEAD {
+                self.next.push([DEAD; 256]);
+                self.terminal.push(None);
+                self.next[node][b as usize] = (self.next.len() - 1) as u32;
+            }
+            node = self.next[node][b as usize] as usize;
+        }
+        if self.terminal[node].is_none() {
+            self.terminal[node] = Some(terminal);
+        }
+    }
+
+    /*
+     * Walk the trie over `bytes`, returning the length and terminal of the patterns passed, skipping
+     * words unless `words` is set. With `longest`, the walk continues to the deepest pattern;
+     * otherwise it stops at the first.
+     */
+    fn walk(
+        &self,
+        bytes: impl Iterator<Item = u8>,
+        words: bool,
+        longest: bool,
+    ) -> Option<(usize, Terminal)> {
+        let mut node: usize = 0;
+        let mut found: Option<(usize, Terminal)> = None;
+        for (depth, b) in bytes.enumerate() {
+            node = self.next[node][b as usize] as usize;
+            if node == DEAD as usize {
+                break;
+            }
+            if let Some(terminal) = self.terminal[node] {
+                if words || !terminal.word {
+                    found = Some((depth + 1, terminal));
+                    if !longest {
+                        break;
+                    }
+                }
+            }
+        }
+        return found;
+    }
+}
+
+impl Matcher {
+    /*
+     * Compile patterns and their digit values. The first `words` patterns are words, and the
+     * rest digit chars.
+     */
+    pub fn new(patterns: &[(String, u32)], words: usize) -> Matcher {
+        let mut forward: Trie = Trie::new();
+        let mut reverse: Trie = Trie::new();
+        for (i, (pattern, value)) in patterns.iter().enumerate() {
+            let terminal: Terminal = Terminal {
+                value: *value,
+                word: i < words,
+            };
+            forward.insert(pattern.bytes(), terminal);
+            reverse.insert(pattern.bytes().rev(), terminal);
+        }
+        return Matcher { forward, reverse };
+    }
+
+    /*
+     * Value of the leftmost pattern in the line, the longest where several start at the same
+     * index. Words count only when `words` is set.
+     */
+    pub fn first(&self, line: &[u8], words: bool) -> Option<u32> {
+        for start in 0..line.len() {
+            let found: Option<(usize, Terminal)> =
+                self.forward
+                    .walk(line[start..].iter().copied(), words, true);
+            if let Some((_, terminal)) = found {
+                return Some(terminal.value);
+            }
+        }
+        return None;
+    }
+
+    /*
+     * Value of the longest word starting at byte `start` of the line. A word never starts with a
+     * digit char, so a digit char matched there means no word is.
+     */
+    pub fn word_at(&self, line: &[u8], start: usize) -> Option<u32> {
+        let (_, terminal): (usize, Terminal) =
+            self.forward
+                .walk(line[start..].iter().copied(), true, true)?;
+        return Some(terminal.value).filter(|_| terminal.word);
+    }
+
+    /*
+     * Value of the rightmost pattern in the line, the longest where several start at the same
+     * index. Ends are scanned from the right with the reversed trie; once no pattern ending
+     * further left can start after the best one found, the scan stops.
+     */
+    pub fn last(&self, line: &[u8], words: bool) -> Option<u32> {
+        let mut best: Option<(usize, u32)> = None; // (start, value)
+        let mut end: usize = line.len();
+        while end > 0 && best.is_none_or(|(start, _)| end - 1 > start) {
+            let found: Option<(usize, Terminal)> =
+                self.reverse
+                    .walk(line[..end].iter().rev().copied(), words, false);
+            if let Some((len, terminal)) = found {
+                // for equal starts, the pattern found first ends further right, so is longer
+                if best.is_none_or(|(start, _)| end - len > start) {
+                    best = Some((end - len, terminal.value));
+                }
+            }
+            end -= 1;
+        }
+        return best.map(|(_, value)| value);
+    }
+}
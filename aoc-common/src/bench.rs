@@ -0,0 +1,100 @@
+//!
+//! Statistical timing of solution stages.
+//!
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+///
+/// Variables, Data Structures, Types
+///
+
+/* Summary statistics of repeated timings. */
+#[derive(Clone, Copy, Debug)]
+pub struct Stats {
+    pub iterations: usize,
+    pub min: Duration,
+    pub mean: Duration,
+    pub median: Duration,
+    pub p95: Duration,
+    pub stddev: Duration,
+}
+
+/* Timings of each stage of a solution. Parts are timed on an already parsed input. */
+#[derive(Clone, Copy, Debug)]
+pub struct StageStats {
+    pub parse: Stats,
+    pub part_one: Stats,
+    pub part_two: Stats,
+}
+
+///
+/// Functions
+///
+
+impl Stats {
+    /* Summarize a set of samples. Panics if there are none. */
+    pub fn from_samples(samples: &mut [Duration]) -> Stats {
+        assert!(!samples.is_empty(), "no samples to summarize");
+        samples.sort();
+        let n: usize = samples.len();
+        let total: f64 = samples.iter().map(|d| d.as_secs_f64()).sum();
+        let mean: f64 = total / n as f64;
+        let variance: f64 = samples
+            .iter()
+            .map(|d| (d.as_secs_f64() - mean).powi(2))
+            .sum::<f64>()
+            / n as f64;
+        let median: Duration = if n % 2 == 1 {
+            samples[n / 2]
+        } else {
+            (samples[n / 2 - 1] + samples[n / 2]) / 2
+        };
+        // nearest-rank percentile
+        let rank: usize = ((0.95 * n as f64).ceil() as usize).clamp(1, n);
+        return Stats {
+            iterations: n,
+            min: samples[0],
+            mean: Duration::from_secs_f64(mean),
+            median,
+            p95: samples[rank - 1],
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        };
+    }
+}
+
+/*
+ * Time `f` over `iterations` runs after `warmup` untimed runs. Results are passed through
+ * `black_box` so the optimizer cannot drop the work being measured.
+ */
+pub fn measure<T, F>(warmup: usize, iterations: usize, mut f: F) -> Stats
+where
+    F: FnMut() -> T,
+{
+    for _ in 0..warmup {
+        black_box(f());
+    }
+    let mut samples: Vec<Duration> = Vec::with_capacity(iterations.max(1));
+    for _ in 0..iterations.max(1) {
+        let start: Instant = Instant::now();
+        black_box(f());
+        samples.push(start.elapsed());
+    }
+    return Stats::from_samples(&mut samples);
+}
+
+/*
+ * Format a duration with a unit suited to its magnitude, e.g. "812 ns", "4.21 ms".
+ */
+pub fn format_duration(d: Duration) -> String {
+    let ns: f64 = d.as_secs_f64() * 1e9;
+    return if ns < 1e3 {
+        format!("{:.0} ns", ns)
+    } else if ns < 1e6 {
+        format!("{:.2} µs", ns / 1e3)
+    } else if ns < 1e9 {
+        format!("{:.2} ms", ns / 1e6)
+    } else {
+        format!("{:.2} s", ns / 1e9)
+    };
+}
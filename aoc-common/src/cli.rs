@@ -0,0 +1,646 @@
+//!
+//! Command-line interface shared by every day. Each binary declares its options once as a `Spec`;
+//! the same spec parses the arguments strictly and generates the `--help` text.
+//!
+
+use std::collections::HashMap;
+use std::env;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use crate::color::colorize;
+use crate::error::Error;
+use crate::solution::Part;
+
+///
+/// Variables, Data Structures, Types
+///
+
+/* Exit code for invalid command-line usage. */
+pub const EXIT_USAGE: i32 = 2;
+
+/*
+ * Command-line option. Options without a `value` name are boolean flags. Options with an
+ * `implicit` value may be given without one, e.g. `--example` for `--example 1`.
+ */
+#[derive(Clone, Copy, Debug)]
+pub struct Opt {
+    pub short: Option<char>,
+    pub long: &'static str,
+    pub value: Option<&'static str>,
+    pub help: &'static str,
+    pub choices: &'static [&'static str],
+    pub default: Option<&'static str>,
+    pub implicit: Option<&'static str>,
+}
+
+/* Positional argument, shown as `<NAME>`, or `[<NAME>]` when optional. */
+#[derive(Clone, Copy, Debug)]
+pub struct Arg {
+    pub name: &'static str,
+    pub help: &'static str,
+    pub required: bool,
+}
+
+/*
+ * Command-line interface of a binary or subcommand: its positional arguments, options,
+ * description, and usage examples. The `name` of a subcommand includes its binary, e.g. `aoc run`.
+ */
+#[derive(Clone, Copy, Debug)]
+pub struct Spec {
+    pub name: &'static str,
+    pub about: &'static str,
+    pub args: &'static [Arg],
+    pub options: &'static [Opt],
+    pub examples: &'static [&'static str],
+}
+
+/* Parsed arguments. Values include the defaults of options that were not given. */
+#[derive(Debug, Default)]
+pub struct Matches {
+    args: HashMap<&'static str, String>,
+    values: HashMap<&'static str, String>,
+    flags: Vec<&'static str>,
+}
+
+/* Command-line usage error. */
+#[derive(Debug, PartialEq, Eq)]
+pub enum CliError {
+    UnknownArgument(String),
+    MissingArgument(&'static str),
+    InvalidArgument {
+        name: &'static str,
+        value: String,
+    },
+    MissingValue(&'static str),
+    UnexpectedValue(&'static str),
+    Conflict(&'static str, &'static str),
+    InvalidValue {
+        option: &'static str,
+        value: String,
+        choices: &'static [&'static str],
+    },
+}
+
+/* Options shared by every day. */
+pub const INPUT: Opt = Opt::value(
+    Some('i'),
+    "input",
+    "PATH",
+    "Path to the input file, or `-` for stdin. Defaults to the day's input.txt.",
+);
+pub const TEXT: Opt = Opt::value(Some('t'), "text", "TEXT", "Use TEXT as the input.");
+pub const EXAMPLE: Opt = Opt::value(
+    Some('e'),
+    "example",
+    "N",
+    "Use the Nth example of the problem statement as the input.",
+)
+.implicit("1");
+pub const PART: Opt = Opt::value(None, "part", "N", "Puzzle part.")
+    .choices(&["1", "2"])
+    .default("1");
+pub const VERBOSE: Opt = Opt::flag(Some('v'), "verbose", "Print debugging information.");
+pub const PROBLEM: Opt = Opt::flag(Some('p'), "problem", "Print problem statement.");
+pub const HELP: Opt = Opt::flag(Some('h'), "help", "Print this help message.");
+
+///
+/// Functions
+///
+
+impl Opt {
+    /* Boolean flag. */
+    pub const fn flag(short: Option<char>, long: &'static str, help: &'static str) -> Opt {
+        return Opt {
+            short,
+            long,
+            value: None,
+            help,
+            choices: &[],
+            default: None,
+            implicit: None,
+        };
+    }
+
+    /* Option that takes a value, shown as `<NAME>` in the help text. */
+    pub const fn value(
+        short: Option<char>,
+        long: &'static str,
+        name: &'static str,
+        help: &'static str,
+    ) -> Opt {
+        let mut opt: Opt = Opt::flag(short, long, help);
+        opt.value = Some(name);
+        return opt;
+    }
+
+    /* Restrict the value to a fixed set of names. */
+    pub const fn choices(mut self, choices: &'static [&'static str]) -> Opt {
+        self.choices = choices;
+        return self;
+    }
+
+    /* Value used when the option is not given. */
+    pub const fn default(mut self, default: &'static str) -> Opt {
+        self.default = Some(default);
+        return self;
+    }
+
+    /* Value used when the option is given without one. */
+    pub const fn implicit(mut self, implicit: &'static str) -> Opt {
+        self.implicit = Some(implicit);
+        return self;
+    }
+
+    /* Left column of the help text, e.g. "-i, --input <PATH>". */
+    fn signature(&self) -> String {
+        let short: String = match self.short {
+            Some(c) => format!("-{}, ", c),
+            None => String::from("    "),
+        };
+        let value: String = match (self.value, self.implicit) {
+            (Some(name), Some(_)) => format!(" [<{}>]", name),
+            (Some(name), None) => format!(" <{}>", name),
+            (None, _) => String::new(),
+        };
+        return format!("{}--{}{}", short, self.long, value);
+    }
+
+    /* Right column of the help text, with the choices and default appended. */
+    fn description(&self) -> String {
+        let mut notes: Vec<String> = Vec::new();
+        if !self.choices.is_empty() {
+            let quoted: Vec<String> = self.choices.iter().map(|c| format!("{:?}", c)).collect();
+            notes.push(format!("options: [{}]", quoted.join(", ")));
+        }
+        if let Some(default) = self.default {
+            notes.push(format!("default: {:?}", default));
+        }
+        if let Some(implicit) = self.implicit {
+            notes.push(format!("alone: {:?}", implicit));
+        }
+        return if notes.is_empty() {
+            String::from(self.help)
+        } else {
+            format!("{} [{}]", self.help, notes.join("; "))
+        };
+    }
+}
+
+impl Arg {
+    /* Argument that must be given. */
+    pub const fn required(name: &'static str, help: &'static str) -> Arg {
+        return Arg {
+            name,
+            help,
+            required: true,
+        };
+    }
+
+    /* Argument that may be left out. */
+    pub const fn optional(name: &'static str, help: &'static str) -> Arg {
+        return Arg {
+            name,
+            help,
+            required: false,
+        };
+    }
+
+    /* Left column of the help text, e.g. "<YEAR>" or "[<DAY>]". */
+    fn signature(&self) -> String {
+        return if self.required {
+            format!("<{}>", self.name)
+        } else {
+            format!("[<{}>]", self.name)
+        };
+    }
+}
+
+impl Spec {
+    /* Find an option by `--long` or `-s` spelling. */
+    fn find(&self, arg: &str) -> Option<&Opt> {
+        if let Some(long) = arg.strip_prefix("--") {
+            return self.options.iter().find(|o| o.long == long);
+        }
+        let mut chars = arg.strip_prefix('-')?.chars();
+        let (short, rest) = (chars.next()?, chars.next());
+        if rest.is_some() {
+            return None;
+        }
+        return self.options.iter().find(|o| o.short == Some(short));
+    }
+
+    /*
+     * Parse arguments, excluding the program name. Unknown options, positional arguments beyond
+     * the declared ones, missing required arguments (unless `--help` is given) and values, and
+     * values outside an option's choices are errors. An option with an implicit value only takes the next argument as its
+     * value if that does not start with `-`.
+     */
+    pub fn parse<I>(&self, args: I) -> Result<Matches, CliError>
+    where
+        I: IntoIterator<Item = String>,
+    {
+        let mut matches: Matches = Matches::default();
+        for opt in self.options {
+            if let Some(default) = opt.default {
+                matches.values.insert(opt.long, String::from(default));
+            }
+        }
+        let mut args = args.into_iter().peekable();
+        while let Some(arg) = args.next() {
+            // accept `--name=value` as well as `--name value`
+            let (name, inline): (&str, Option<String>) = match arg.split_once('=') {
+                Some((n, v)) if n.starts_with("--") => (n, Some(String::from(v))),
+                _ => (&arg[..], None),
+            };
+            let opt: &Opt = match self.find(name) {
+                Some(opt) => opt,
+                None if !arg.starts_with('-') && matches.args.len() < self.args.len() => {
+                    matches.args.insert(self.args[matches.args.len()].name, arg);
+                    continue;
+                }
+                None => return Err(CliError::UnknownArgument(arg)),
+            };
+            if opt.value.is_none() {
+                if inline.is_some() {
+                    return Err(CliError::UnexpectedValue(opt.long));
+                }
+                matches.flags.push(opt.long);
+                continue;
+            }
+            let value: String = match (inline, opt.implicit) {
+                (Some(v), _) => v,
+                (None, Some(implicit)) => match args.next_if(|v| !v.starts_with('-')) {
+                    Some(v) => v,
+                    None => String::from(implicit),
+                },
+                // the next option is not taken as the value, e.g. `--input --verbose`
+                (None, None) => match args.next() {
+                    Some(v) if self.find(&v).is_none() => v,
+                    _ => return Err(CliError::MissingValue(opt.long)),
+                },
+            };
+            if !opt.choices.is_empty() && !opt.choices.contains(&value.as_str()) {
+                return Err(CliError::InvalidValue {
+                    option: opt.long,
+                    value,
+                    choices: opt.choices,
+                });
+            }
+            matches.values.insert(opt.long, value);
+        }
+        // `--help` is answered without the required arguments
+        let missing: Option<&Arg> = self.args.get(matches.args.len()).filter(|a| a.required);
+        if let (Some(arg), false) = (missing, matches.flag(HELP.long)) {
+            return Err(CliError::MissingArgument(arg.name));
+        }
+        return Ok(matches);
+    }
+
+    /*
+     * Parse the process arguments. Prints the help text and exits for `--help`, and prints the
+     * error and exits with `EXIT_USAGE` for invalid arguments.
+     */
+    pub fn parse_env(&self) -> Matches {
+        match self.parse(env::args().skip(1)) {
+            Ok(matches) => {
+                if matches.flag(HELP.long) {
+                    help(&self.help());
+                    std::process::exit(0);
+                }
+                return matches;
+            }
+            Err(e) => {
+                eprintln!("{}: {}", colorize("error", "red", true, false), e);
+                eprintln!("Run `{} --help` for usage.", self.name);
+                std::process::exit(EXIT_USAGE);
+            }
+        }
+    }
+
+    /*
+     * Parse the arguments of a subcommand, excluding the subcommand itself. Prints the help text
+     * and returns none for `--help`.
+     */
+    pub fn parse_command(&self, args: &[String]) -> Result<Option<Matches>, CliError> {
+        let matches: Matches = self.parse(args.iter().cloned())?;
+        if matches.flag(HELP.long) {
+            help(&self.help());
+            return Ok(None);
+        }
+        return Ok(Some(matches));
+    }
+
+    /* Positional arguments as written in a usage line, e.g. "<YEAR> [<DAY>]". */
+    pub fn synopsis(&self) -> String {
+        let args: Vec<String> = self.args.iter().map(|a| a.signature()).collect();
+        return args.join(" ");
+    }
+
+    /* Help text generated from the arguments and options. */
+    pub fn help(&self) -> String {
+        let program: String = match self.name.split_once(' ') {
+            Some((binary, command)) => format!("{}[EXE] {}", binary, command),
+            None => format!("{}[EXE]", self.name),
+        };
+        let mut usage: String = format!("Usage: {}", program);
+        if !self.args.is_empty() {
+            usage.push_str(&format!(" {}", self.synopsis()));
+        }
+        usage.push_str(" [OPTIONS]");
+        for opt in self.options {
+            match (opt.value, opt.implicit) {
+                (Some(name), Some(_)) => usage.push_str(&format!(" [--{} [<{}>]]", opt.long, name)),
+                (Some(name), None) => usage.push_str(&format!(" [--{} <{}>]", opt.long, name)),
+                (None, _) => usage.push_str(&format!(" [--{}]", opt.long)),
+            }
+        }
+        let mut text: String = format!("\n{}\n\n{}\n", usage, self.about);
+        if !self.args.is_empty() {
+            text.push_str("\nArguments:\n");
+            for arg in self.args {
+                text.push_str(&format!("  {:<24}  {}\n", arg.signature(), arg.help));
+            }
+        }
+        text.push_str("\nOptions:\n");
+        for opt in self.options {
+            text.push_str(&format!(
+                "  {:<24}  {}\n",
+                opt.signature(),
+                opt.description()
+            ));
+        }
+        if !self.examples.is_empty() {
+            text.push_str("\nExamples:\n");
+            for example in self.examples {
+                text.push_str(&format!("  $ {}\n", example));
+            }
+        }
+        return text;
+    }
+}
+
+impl Matches {
+    /* Value of an option, or its default. */
+    pub fn value(&self, long: &str) -> Option<&str> {
+        return self.values.get(long).map(|v| v.as_str());
+    }
+
+    /* Value of a positional argument, if given. */
+    pub fn arg(&self, name: &str) -> Option<&str> {
+        return self.args.get(name).map(|v| v.as_str());
+    }
+
+    /* Positional argument parsed as a number or other type, if given. */
+    pub fn arg_as<T: FromStr>(&self, name: &'static str) -> Result<Option<T>, CliError> {
+        return match self.arg(name) {
+            Some(v) => v.parse().map(Some).map_err(|_| CliError::InvalidArgument {
+                name,
+                value: String::from(v),
+            }),
+            None => Ok(None),
+        };
+    }
+
+    /* Value of an option, or its default, parsed as a number or other type. */
+    pub fn value_as<T: FromStr>(&self, long: &'static str) -> Result<Option<T>, CliError> {
+        return match self.value(long) {
+            Some(v) => v.parse().map(Some).map_err(|_| CliError::InvalidValue {
+                option: long,
+                value: String::from(v),
+                choices: &[],
+            }),
+            None => Ok(None),
+        };
+    }
+
+    /* Value of the shared `--part` option. */
+    pub fn part(&self) -> Part {
+        return self
+            .value(PART.long)
+            .and_then(|p| p.parse::<u8>().ok())
+            .and_then(Part::from_number)
+            .unwrap_or(Part::One);
+    }
+
+    /* Whether a boolean flag was given. */
+    pub fn flag(&self, long: &str) -> bool {
+        return self.flags.contains(&long);
+    }
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CliError::UnknownArgument(arg) => write!(f, "unknown argument: {}", arg),
+            CliError::MissingArgument(name) => write!(f, "missing <{}>", name),
+            CliError::InvalidArgument { name, value } => {
+                write!(f, "invalid value {:?} for <{}>", value, name)
+            }
+            CliError::MissingValue(long) => write!(f, "missing value for --{}", long),
+            CliError::UnexpectedValue(long) => write!(f, "--{} does not take a value", long),
+            CliError::Conflict(a, b) => write!(f, "--{} cannot be combined with --{}", a, b),
+            CliError::InvalidValue {
+                option,
+                value,
+                choices: [],
+            } => write!(f, "invalid value {:?} for --{}", value, option),
+            CliError::InvalidValue {
+                option,
+                value,
+                choices,
+            } => write!(
+                f,
+                "invalid value {:?} for --{} (expected one of: {})",
+                value,
+                option,
+                choices.join(", ")
+            ),
+        }
+    }
+}
+
+impl std::error::Error for CliError {}
+
+/*
+ * Print the problem statement found in the day directory. A missing or unreadable file is an
+ * I/O error naming it.
+ */
+pub fn problem(dir: &Path) -> crate::error::Result<()> {
+    let filepath: PathBuf = dir.join("problem.txt");
+    let statement: String =
+        std::fs::read_to_string(&filepath).map_err(|e| Error::from(e).with_path(&filepath))?;
+    println!("\n{}\n", colorize(&statement, "cyan", false, false));
+    return Ok(());
+}
+
+/*
+ * Print the banner found in the day directory. A missing or unreadable file is an I/O error
+ * naming it.
+ */
+pub fn banner(dir: &Path) -> crate::error::Result<()> {
+    let filepath: PathBuf = dir.join("banner.txt");
+    let banner: String =
+        std::fs::read_to_string(&filepath).map_err(|e| Error::from(e).with_path(&filepath))?;
+    println!("\n{}\n", colorize(&banner, "cyan", false, false));
+    return Ok(());
+}
+
+/*
+ * Print help message.
+ */
+pub fn help(usage: &str) {
+    println!("{}", colorize(usage, "cyan", false, false));
+}
+
+///
+/// Tests
+///
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /* Spec with a required and an optional argument and options of every kind. */
+    static SPEC: Spec = Spec {
+        name: "test",
+        about: "Test spec.",
+        args: &[Arg::required("YEAR", "Year."), Arg::optional("DAY", "Day.")],
+        options: &[
+            INPUT,
+            EXAMPLE,
+            PART,
+            VERBOSE,
+            Opt::value(None, "sum", "NAME", "Method.").choices(&["array", "trie"]),
+            HELP,
+        ],
+        examples: &[],
+    };
+
+    /* Parse arguments given as one string split on spaces. */
+    fn parse(args: &str) -> Result<Matches, CliError> {
+        return SPEC.parse(args.split_whitespace().map(String::from));
+    }
+
+    #[test]
+    fn parses_arguments_options_and_defaults() {
+        let matches: Matches = parse("2023 1 --input in.txt -v --sum=trie").expect("valid");
+        assert_eq!(matches.arg("YEAR"), Some("2023"));
+        assert_eq!(matches.arg_as::<u8>("DAY"), Ok(Some(1)));
+        assert_eq!(matches.value(INPUT.long), Some("in.txt"));
+        assert_eq!(matches.value("sum"), Some("trie"));
+        assert_eq!(matches.part(), Part::One);
+        assert!(matches.flag(VERBOSE.long));
+        assert!(!matches.flag(HELP.long));
+        let matches: Matches = parse("2023").expect("valid");
+        assert_eq!(matches.arg("DAY"), None);
+        assert_eq!(matches.value(INPUT.long), None);
+    }
+
+    #[test]
+    fn rejects_unknown_flags_and_extra_arguments() {
+        for (args, unknown) in [
+            ("2023 --bogus", "--bogus"),
+            ("2023 -x", "-x"),
+            ("2023 -vi", "-vi"),
+            ("2023 1 2", "2"),
+        ] {
+            assert_eq!(
+                parse(args).map(|_| ()),
+                Err(CliError::UnknownArgument(String::from(unknown)))
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_missing_arguments_and_values() {
+        assert_eq!(
+            parse("--verbose").map(|_| ()),
+            Err(CliError::MissingArgument("YEAR"))
+        );
+        assert!(parse("--help").is_ok(), "--help needs no arguments");
+        assert_eq!(
+            parse("2023 --input").map(|_| ()),
+            Err(CliError::MissingValue("input"))
+        );
+        // the next option is not taken as the value
+        assert_eq!(
+            parse("2023 --input --verbose").map(|_| ()),
+            Err(CliError::MissingValue("input"))
+        );
+        assert_eq!(
+            parse("2023 --input -e").map(|_| ()),
+            Err(CliError::MissingValue("input"))
+        );
+        // a value that only looks like an option is taken, e.g. stdin
+        let matches: Matches = parse("2023 --input -").expect("valid");
+        assert_eq!(matches.value(INPUT.long), Some("-"));
+    }
+
+    #[test]
+    fn rejects_invalid_choices_and_values() {
+        assert_eq!(
+            parse("2023 --sum tree").map(|_| ()),
+            Err(CliError::InvalidValue {
+                option: "sum",
+                value: String::from("tree"),
+                choices: &["array", "trie"],
+            })
+        );
+        assert_eq!(
+            parse("2023 --part=3").map(|_| ()),
+            Err(CliError::InvalidValue {
+                option: "part",
+                value: String::from("3"),
+                choices: &["1", "2"],
+            })
+        );
+        let matches: Matches = parse("twenty --example x").expect("valid");
+        assert_eq!(
+            matches.arg_as::<u16>("YEAR"),
+            Err(CliError::InvalidArgument {
+                name: "YEAR",
+                value: String::from("twenty"),
+            })
+        );
+        assert!(matches.value_as::<usize>(EXAMPLE.long).is_err());
+    }
+
+    #[test]
+    fn rejects_a_value_on_a_boolean_flag() {
+        assert_eq!(
+            parse("2023 --verbose=yes").map(|_| ()),
+            Err(CliError::UnexpectedValue("verbose"))
+        );
+    }
+
+    #[test]
+    fn implicit_value_is_used_when_none_follows() {
+        for (args, example, day) in [
+            ("2023 --example", "1", None),
+            ("2023 -e 3", "3", None),
+            ("2023 --example=2", "2", None),
+            ("2023 --example --verbose", "1", None),
+            // the value is optional, so a later positional argument is taken as it
+            ("2023 --example 5 1", "5", Some("1")),
+        ] {
+            let matches: Matches = parse(args).expect(args);
+            assert_eq!(matches.value(EXAMPLE.long), Some(example), "{}", args);
+            assert_eq!(matches.arg("DAY"), day, "{}", args);
+        }
+        // `-` is not taken as the example, and is then left over as an unknown argument
+        assert!(parse("2023 --example - 1").is_err());
+    }
+
+    #[test]
+    fn help_lists_arguments_and_options() {
+        let text: String = SPEC.help();
+        assert!(text.contains("Usage: test[EXE] <YEAR> [<DAY>] [OPTIONS]"));
+        assert!(text.contains("  [<DAY>]  "));
+        assert!(text.contains("-e, --example [<N>]"));
+        assert!(text.contains("[options: [\"array\", \"trie\"]]"));
+        assert!(text.contains("[options: [\"1\", \"2\"]; default: \"1\"]"));
+        assert_eq!(SPEC.synopsis(), "<YEAR> [<DAY>]");
+    }
+}
@@ -0,0 +1,41 @@
+//!
+//! Terminal colors.
+//!
+
+/*
+ * Colorize strings using ANSI escape sequences.
+ * -------------------------------------------------------------------
+ * | Color   | Text | Background | Bright Text  |  Bright Background |
+ * -------------------------------------------------------------------
+ * | Black   |  30  | 40         | 90           | 100                |
+ * | Red     |  31  | 41         | 91           | 101                |
+ * | Green   |  32  | 42         | 92           | 102                |
+ * | Yellow  |  33  | 43         | 93           | 103                |
+ * | Blue    |  34  | 44         | 94           | 104                |
+ * | Magenta |  35  | 45         | 95           | 105                |
+ * | Cyan    |  36  | 46         | 96           | 106                |
+ * | White   |  37  | 47         | 97           | 107                |
+ * | Default |  39  | 49         | 99           | 109                |
+ * -------------------------------------------------------------------
+ */
+pub fn colorize(text: &str, color: &str, bright: bool, back: bool) -> String {
+    let mut code: u8 = match color {
+        "black" => 30,
+        "red" => 31,
+        "green" => 32,
+        "yellow" => 33,
+        "blue" => 34,
+        "magenta" => 35,
+        "cyan" => 36,
+        "white" => 37,
+        "default" => 39,
+        &_ => 39, // set to default if no match found
+    };
+    if back {
+        code += 10;
+    }
+    if bright {
+        code += 60;
+    }
+    return format!("\x1b[{}m{}\x1b[0m", code, text);
+}
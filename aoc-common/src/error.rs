@@ -0,0 +1,263 @@
+//!
+//! Error type shared by every day and the runner. Input errors carry the path, line, and column
+//! they were found at, and each class of error exits with its own code.
+//!
+
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::Utf8Error;
+
+use crate::cli::{CliError, EXIT_USAGE};
+use crate::color::colorize;
+
+///
+/// Variables, Data Structures, Types
+///
+
+/* Position in a puzzle input. Lines and columns are 1-based; columns count chars. */
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Location {
+    pub path: Option<PathBuf>,
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+}
+
+/* Error classes. */
+#[derive(Debug)]
+pub enum Error {
+    /* Invalid command-line usage. */
+    Usage(String),
+    /* Failure to read or write a file. */
+    Io {
+        path: Option<PathBuf>,
+        source: io::Error,
+    },
+    /* Input that is not valid UTF-8. */
+    Decode {
+        location: Location,
+        source: Utf8Error,
+    },
+    /* Input that does not match the puzzle format. */
+    Parse {
+        location: Location,
+        message: String,
+    },
+    /* Line without a digit to form a calibration value. */
+    MissingDigit {
+        location: Location,
+        text: String,
+    },
+    /* Value computed for a line that disagrees with a reference implementation. */
+    Mismatch {
+        location: Location,
+        text: String,
+        message: String,
+    },
+    /* Running total that no longer fits its integer type when a line is added. */
+    Overflow {
+        location: Location,
+        text: String,
+        message: String,
+    },
+    /* Anything else, such as an unregistered day. */
+    Other(String),
+}
+
+/* Result type returned by solutions and tooling. */
+pub type Result<T> = std::result::Result<T, Error>;
+
+///
+/// Functions
+///
+
+impl Location {
+    /* Location of a whole line. */
+    pub fn line(line: usize) -> Location {
+        return Location {
+            path: None,
+            line: Some(line),
+            column: None,
+        };
+    }
+
+    /* Location of a char within a line. */
+    pub fn at(line: usize, column: usize) -> Location {
+        return Location {
+            path: None,
+            line: Some(line),
+            column: Some(column),
+        };
+    }
+
+    /*
+     * Location just past the end of `bytes`, e.g. of the first invalid byte following a valid
+     * UTF-8 prefix.
+     */
+    pub fn end_of(bytes: &[u8]) -> Location {
+        let start: usize = bytes.iter().rposition(|b| *b == b'\n').map_or(0, |i| i + 1);
+        let line: usize = bytes.iter().filter(|b| **b == b'\n').count() + 1;
+        let column: usize = String::from_utf8_lossy(&bytes[start..]).chars().count() + 1;
+        return Location::at(line, column);
+    }
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut parts: Vec<String> = Vec::new();
+        parts.push(match &self.path {
+            Some(path) => path.display().to_string(),
+            None => String::from("<input>"),
+        });
+        if let Some(line) = self.line {
+            parts.push(line.to_string());
+        }
+        if let Some(column) = self.column {
+            parts.push(column.to_string());
+        }
+        write!(f, "{}", parts.join(":"))
+    }
+}
+
+impl Error {
+    /* Parse error at a line and column. */
+    pub fn parse(line: usize, column: usize, message: &str) -> Error {
+        return Error::Parse {
+            location: Location::at(line, column),
+            message: String::from(message),
+        };
+    }
+
+    /*
+     * Exit code of the error class: 1 other, 2 usage, 3 I/O, 4 decoding, 5 parse, 6 missing digit,
+     * 7 mismatch, 8 overflow.
+     */
+    pub fn exit_code(&self) -> i32 {
+        return match self {
+            Error::Other(_) => 1,
+            Error::Usage(_) => EXIT_USAGE,
+            Error::Io { .. } => 3,
+            Error::Decode { .. } => 4,
+            Error::Parse { .. } => 5,
+            Error::MissingDigit { .. } => 6,
+            Error::Mismatch { .. } => 7,
+            Error::Overflow { .. } => 8,
+        };
+    }
+
+    /* Attach the input path to an error that does not have one yet. */
+    pub fn with_path(mut self, path: &Path) -> Error {
+        match &mut self {
+            Error::Io { path: p, .. } => {
+                p.get_or_insert_with(|| path.to_path_buf());
+            }
+            Error::Decode { location, .. }
+            | Error::Parse { location, .. }
+            | Error::MissingDigit { location, .. }
+            | Error::Mismatch { location, .. }
+            | Error::Overflow { location, .. } => {
+                location.path.get_or_insert_with(|| path.to_path_buf());
+            }
+            _ => {}
+        }
+        return self;
+    }
+
+    /* Input line the error points at, if it has one. */
+    fn snippet(&self) -> Option<(usize, Option<usize>, &str)> {
+        return match self {
+            Error::MissingDigit { location, text }
+            | Error::Mismatch { location, text, .. }
+            | Error::Overflow { location, text, .. } => location
+                .line
+                .map(|line| (line, location.column, text.as_str())),
+            _ => None,
+        };
+    }
+
+    /*
+     * Multi-line diagnostic for the terminal: the error, and the offending input line with a
+     * caret under the column when known.
+     */
+    pub fn diagnostic(&self) -> String {
+        let mut text: String = format!("{}: {}", colorize("error", "red", true, false), self);
+        if let Some((line, column, snippet)) = self.snippet() {
+            let gutter: String = " ".repeat(line.to_string().len());
+            text.push_str(&format!("\n{} |\n{} | {}", gutter, line, snippet));
+            if let Some(column) = column {
+                let caret: String = format!("{}^", " ".repeat(column - 1));
+                text.push_str(&format!(
+                    "\n{} | {}",
+                    gutter,
+                    colorize(&caret, "red", true, false)
+                ));
+            }
+        }
+        return text;
+    }
+
+    /* Print the diagnostic and exit with the code of the error class. */
+    pub fn exit(&self) -> ! {
+        eprintln!("{}", self.diagnostic());
+        std::process::exit(self.exit_code());
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Usage(message) => write!(f, "{}", message),
+            Error::Io {
+                path: Some(path),
+                source,
+            } => write!(f, "{}: {}", path.display(), source),
+            Error::Io { path: None, source } => write!(f, "{}", source),
+            Error::Decode { location, source } => {
+                write!(f, "{}: invalid UTF-8: {}", location, source)
+            }
+            Error::Parse { location, message } => write!(f, "{}: {}", location, message),
+            Error::MissingDigit { location, .. } => write!(f, "{}: no digit in line", location),
+            Error::Mismatch {
+                location, message, ..
+            }
+            | Error::Overflow {
+                location, message, ..
+            } => write!(f, "{}: {}", location, message),
+            Error::Other(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        return match self {
+            Error::Io { source, .. } => Some(source),
+            Error::Decode { source, .. } => Some(source),
+            _ => None,
+        };
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(source: io::Error) -> Error {
+        return Error::Io { path: None, source };
+    }
+}
+
+impl From<CliError> for Error {
+    fn from(e: CliError) -> Error {
+        return Error::Usage(e.to_string());
+    }
+}
+
+impl From<String> for Error {
+    fn from(message: String) -> Error {
+        return Error::Other(message);
+    }
+}
+
+impl From<&str> for Error {
+    fn from(message: &str) -> Error {
+        return Error::Other(String::from(message));
+    }
+}
@@ -0,0 +1,472 @@
+//!
+//! Input reading.
+//!
+
+use std::fmt;
+use std::fs::File;
+use std::io::Result as IoResult;
+use std::io::{BufRead, BufReader, Lines, Read};
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+
+use crate::cli::{CliError, Matches, EXAMPLE, INPUT, TEXT};
+use crate::error::{Error, Location, Result};
+
+///
+/// Variables, Data Structures, Types
+///
+
+/* Where a puzzle input comes from. */
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Source {
+    /* File at a path. */
+    File(PathBuf),
+    /* Standard input, read to the end. */
+    Stdin,
+    /* Text given on the command line. */
+    Text(String),
+    /* Nth example of the problem statement, counting from 1. */
+    Example(usize),
+}
+
+/*
+ * Raw bytes of a puzzle input: a memory-mapped file on Linux, or bytes read into memory. Either
+ * derefs to `&[u8]`, and `text` borrows it as UTF-8 without copying, so inputs of any size are
+ * only held once.
+ */
+#[derive(Debug)]
+pub enum Buffer {
+    #[cfg(target_os = "linux")]
+    Mapped(memmap2::Mmap),
+    Owned(Vec<u8>),
+}
+
+///
+/// Functions
+///
+
+/*
+ * Read lines from a file path. Output is wrapped in a Result to allow matching on errors.
+ * Returns an Iterator to the Reader of the lines of the file.
+ */
+pub fn read_lines<P>(filename: P) -> IoResult<Lines<BufReader<File>>>
+where
+    P: AsRef<Path>,
+{
+    let file = File::open(filename)?;
+    Ok(BufReader::new(file).lines())
+}
+
+/*
+ * Decode puzzle input bytes as UTF-8 text. Invalid UTF-8 is reported at its line and column.
+ */
+fn decode(bytes: Vec<u8>) -> Result<String> {
+    return String::from_utf8(bytes).map_err(|e| decode_error(e.as_bytes(), e.utf8_error()));
+}
+
+/*
+ * Decoding error located at the end of the valid UTF-8 prefix of the bytes.
+ */
+fn decode_error(bytes: &[u8], source: std::str::Utf8Error) -> Error {
+    let location: Location = Location::end_of(&bytes[..source.valid_up_to()]);
+    return Error::Decode { location, source };
+}
+
+/*
+ * Split text into at most `n` chunks of whole lines, of about equal size, for processing in
+ * parallel. Each chunk but the last ends with a newline.
+ */
+pub fn line_chunks(text: &str, n: usize) -> Vec<&str> {
+    let size: usize = text.len().div_ceil(n.max(1)).max(1);
+    let mut chunks: Vec<&str> = Vec::new();
+    let mut rest: &str = text;
+    while !rest.is_empty() {
+        // the chunk ends with the first line that reaches `size` bytes, so there are at most `n`
+        let end: usize = match rest.as_bytes().get(size - 1..) {
+            Some(tail) => tail
+                .iter()
+                .position(|b| *b == b'\n')
+                .map_or(rest.len(), |i| size + i),
+            None => rest.len(),
+        };
+        let (chunk, tail): (&str, &str) = rest.split_at(end);
+        chunks.push(chunk);
+        rest = tail;
+    }
+    return chunks;
+}
+
+/*
+ * Map a puzzle input file into memory on Linux. Where mapping fails, as for empty files, pipes,
+ * and some special files, and on other platforms, the file is read into memory instead.
+ */
+pub fn map_input(path: &Path) -> Result<Buffer> {
+    let mut file: File = File::open(path).map_err(|e| Error::from(e).with_path(path))?;
+    #[cfg(target_os = "linux")]
+    {
+        // SAFETY: the map is read-only. It is undefined behavior for the file to be truncated or
+        // modified while it is mapped, which inputs are not while they are being solved.
+        if let Ok(map) = unsafe { memmap2::Mmap::map(&file) } {
+            // lines are read front to back; a failed hint changes nothing
+            let _ = map.advise(memmap2::Advice::Sequential);
+            return Ok(Buffer::Mapped(map));
+        }
+    }
+    let mut bytes: Vec<u8> = Vec::new();
+    file.read_to_end(&mut bytes)
+        .map_err(|e| Error::from(e).with_path(path))?;
+    return Ok(Buffer::Owned(bytes));
+}
+
+/*
+ * Read a puzzle input file as UTF-8 text. Invalid UTF-8 is reported at its line and column.
+ */
+pub fn read_input(path: &Path) -> Result<String> {
+    let bytes: Vec<u8> = std::fs::read(path).map_err(|e| Error::from(e).with_path(path))?;
+    return decode(bytes).map_err(|e| e.with_path(path));
+}
+
+/*
+ * Example inputs of a day's problem statement, in order. Fenced code blocks of the day's
+ * `README.md` are preferred; otherwise the blocks of `problem.txt` that follow a line ending in
+ * "example:" are used, each running to the next blank line.
+ */
+pub fn examples(dir: &Path) -> Result<Vec<String>> {
+    let readme: PathBuf = dir.join("README.md");
+    if readme.exists() {
+        let blocks: Vec<String> = fenced_blocks(&read_input(&readme)?);
+        if !blocks.is_empty() {
+            return Ok(blocks);
+        }
+    }
+    let text: String = read_input(&dir.join("problem.txt"))?;
+    let mut blocks: Vec<String> = Vec::new();
+    let mut lines = text.lines().peekable();
+    while let Some(line) = lines.next() {
+        if !line.trim_end().to_lowercase().ends_with("example:") {
+            continue;
+        }
+        while lines.next_if(|l| l.trim().is_empty()).is_some() {}
+        let mut block: String = String::new();
+        while let Some(l) = lines.next_if(|l| !l.trim().is_empty()) {
+            block.push_str(l);
+            block.push('\n');
+        }
+        if !block.is_empty() {
+            blocks.push(block);
+        }
+    }
+    return Ok(blocks);
+}
+
+/*
+ * Contents of the fenced code blocks of a Markdown document.
+ */
+fn fenced_blocks(markdown: &str) -> Vec<String> {
+    let mut blocks: Vec<String> = Vec::new();
+    let mut block: Option<String> = None;
+    for line in markdown.lines() {
+        if line.trim_start().starts_with("```") {
+            match block.take() {
+                Some(b) => blocks.push(b),
+                None => block = Some(String::new()),
+            }
+            continue;
+        }
+        if let Some(b) = block.as_mut() {
+            b.push_str(line);
+            b.push('\n');
+        }
+    }
+    return blocks;
+}
+
+impl Source {
+    /*
+     * Source selected by the shared `--input`, `--text`, and `--example` options, which are
+     * mutually exclusive. Without any of them, the day's `input.txt` in `dir` is read.
+     */
+    pub fn from_matches(matches: &Matches, dir: &Path) -> std::result::Result<Source, CliError> {
+        let given: Vec<&'static str> = [INPUT.long, TEXT.long, EXAMPLE.long]
+            .into_iter()
+            .filter(|long| matches.value(long).is_some())
+            .collect();
+        if given.len() > 1 {
+            return Err(CliError::Conflict(given[0], given[1]));
+        }
+        if let Some(path) = matches.value(INPUT.long) {
+            return Ok(Source::from(path));
+        }
+        if let Some(text) = matches.value(TEXT.long) {
+            return Ok(Source::Text(String::from(text)));
+        }
+        if let Some(n) = matches.value(EXAMPLE.long) {
+            return match n.parse::<usize>() {
+                Ok(n) if n > 0 => Ok(Source::Example(n)),
+                _ => Err(CliError::InvalidValue {
+                    option: EXAMPLE.long,
+                    value: String::from(n),
+                    choices: &[],
+                }),
+            };
+        }
+        return Ok(Source::File(dir.join("input.txt")));
+    }
+
+    /*
+     * Read the input as UTF-8 text. `dir` is the day directory holding the problem statement
+     * that examples are taken from. Errors name the source.
+     */
+    pub fn read(&self, dir: &Path) -> Result<String> {
+        let text: Result<String> = match self {
+            Source::File(path) => read_input(path),
+            Source::Stdin => {
+                let mut bytes: Vec<u8> = Vec::new();
+                std::io::stdin().lock().read_to_end(&mut bytes)?;
+                decode(bytes)
+            }
+            Source::Text(text) => Ok(text.clone()),
+            Source::Example(n) => {
+                let examples: Vec<String> = examples(dir)?;
+                let count: usize = examples.len();
+                match examples.into_iter().nth(n.saturating_sub(1)) {
+                    Some(example) if *n > 0 => Ok(example),
+                    _ if count == 0 => {
+                        Err(Error::Usage(format!("no examples in {}", dir.display())))
+                    }
+                    _ => Err(Error::Usage(format!(
+                        "no example {} in {} (--example 1 to {})",
+                        n,
+                        dir.display(),
+                        count
+                    ))),
+                }
+            }
+        };
+        return text.map_err(|e| e.with_path(&self.name()));
+    }
+
+    /*
+     * Load the input without decoding it. A file is memory-mapped where possible; see
+     * `map_input`. Errors name the source.
+     */
+    pub fn load(&self, dir: &Path) -> Result<Buffer> {
+        return match self {
+            Source::File(path) => map_input(path),
+            _ => Ok(Buffer::Owned(self.read(dir)?.into_bytes())),
+        };
+    }
+
+    /* Name of the source used in place of a path in errors, e.g. `<stdin>`. */
+    pub fn name(&self) -> PathBuf {
+        return match self {
+            Source::File(path) => path.clone(),
+            _ => PathBuf::from(self.to_string()),
+        };
+    }
+}
+
+impl From<&str> for Source {
+    /* Path of an input file, or `-` for stdin. */
+    fn from(path: &str) -> Source {
+        if path == "-" {
+            return Source::Stdin;
+        }
+        return Source::File(PathBuf::from(path));
+    }
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Source::File(path) => write!(f, "{}", path.display()),
+            Source::Stdin => write!(f, "<stdin>"),
+            Source::Text(_) => write!(f, "<text>"),
+            Source::Example(n) => write!(f, "<example {}>", n),
+        }
+    }
+}
+
+impl Buffer {
+    /*
+     * The input as UTF-8 text borrowed from the buffer. Invalid UTF-8 is reported at its line and
+     * column.
+     */
+    pub fn text(&self) -> Result<&str> {
+        return std::str::from_utf8(self).map_err(|e| decode_error(self, e));
+    }
+}
+
+impl Deref for Buffer {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        return match self {
+            #[cfg(target_os = "linux")]
+            Buffer::Mapped(map) => map,
+            Buffer::Owned(bytes) => bytes,
+        };
+    }
+}
+
+/*
+ * Find the repository root: the nearest ancestor of the working directory with a Cargo
+ * workspace manifest. Falls back to the parent of this crate when run from elsewhere.
+ */
+pub fn workspace_root() -> PathBuf {
+    if let Ok(cwd) = std::env::current_dir() {
+        for dir in cwd.ancestors() {
+            let manifest: PathBuf = dir.join("Cargo.toml");
+            if let Ok(text) = std::fs::read_to_string(&manifest) {
+                if text.contains("[workspace]") {
+                    return dir.to_path_buf();
+                }
+            }
+        }
+    }
+    return Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+}
+
+/*
+ * Directory of a day's puzzle files, e.g. `<root>/2023/day_01`.
+ */
+pub fn day_dir(root: &Path, year: u16, day: u8) -> PathBuf {
+    return root.join(year.to_string()).join(format!("day_{:02}", day));
+}
+
+///
+/// Tests
+///
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /* Assert that the chunks join back into the text and each but the last ends a line. */
+    fn assert_chunks(text: &str, n: usize) -> Vec<&str> {
+        let chunks: Vec<&str> = line_chunks(text, n);
+        assert_eq!(chunks.concat(), text, "{} chunks of {:?}", n, text);
+        assert!(chunks.len() <= n.max(1), "{} chunks of {:?}", n, text);
+        assert!(chunks.iter().all(|c| !c.is_empty()));
+        if let Some((_, init)) = chunks.split_last() {
+            assert!(init.iter().all(|c| c.ends_with('\n')));
+        }
+        return chunks;
+    }
+
+    /* Fresh day directory holding the given files, removed first if left over from a past run. */
+    fn day_with(name: &str, files: &[(&str, &str)]) -> PathBuf {
+        let dir: PathBuf =
+            std::env::temp_dir().join(format!("aoc-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).expect("create day directory");
+        for (file, text) in files {
+            std::fs::write(dir.join(file), text).expect("write day file");
+        }
+        return dir;
+    }
+
+    #[test]
+    fn examples_come_from_readme_fenced_blocks() {
+        let readme: &str =
+            "# Day 1\n\n```\n1abc2\ntreb7uchet\n```\n\ntext\n\n```text\ntwo1nine\n```\n";
+        let problem: &str = "For example:\n\nnot used\n";
+        let dir: PathBuf = day_with("readme", &[("README.md", readme), ("problem.txt", problem)]);
+        assert_eq!(
+            examples(&dir).expect("examples"),
+            ["1abc2\ntreb7uchet\n", "two1nine\n"]
+        );
+        assert_eq!(
+            Source::Example(2).read(&dir).expect("example 2"),
+            "two1nine\n"
+        );
+        std::fs::remove_dir_all(&dir).expect("remove day directory");
+    }
+
+    #[test]
+    fn examples_fall_back_to_problem_statement() {
+        let readme: &str = "# Day 1\n\nNo code blocks.\n";
+        let problem: &str = "Intro.\nFor example:\n\n1abc2\npqr3stu8vwx\n\nMore text.\nConsider this EXAMPLE:\n  \ntwo1nine\n";
+        let dir: PathBuf = day_with(
+            "problem",
+            &[("README.md", readme), ("problem.txt", problem)],
+        );
+        assert_eq!(
+            examples(&dir).expect("examples"),
+            ["1abc2\npqr3stu8vwx\n", "two1nine\n"]
+        );
+        std::fs::remove_file(dir.join("README.md")).expect("remove README");
+        assert_eq!(examples(&dir).expect("examples").len(), 2);
+        std::fs::remove_dir_all(&dir).expect("remove day directory");
+    }
+
+    #[test]
+    fn missing_example_is_a_usage_error() {
+        let dir: PathBuf = day_with("missing", &[("README.md", "```\n1abc2\n```\n")]);
+        for n in [0, 2, 3] {
+            match Source::Example(n).read(&dir) {
+                Err(Error::Usage(message)) => assert!(message.contains("--example 1 to 1")),
+                other => panic!("example {}: expected a usage error, got {:?}", n, other),
+            }
+        }
+        assert_eq!(
+            Source::Example(2).read(&dir).map_err(|e| e.exit_code()),
+            Err(2)
+        );
+        std::fs::write(dir.join("README.md"), "No code blocks.\n").expect("write README");
+        std::fs::write(dir.join("problem.txt"), "No examples.\n").expect("write problem");
+        assert!(matches!(
+            Source::Example(1).read(&dir),
+            Err(Error::Usage(_))
+        ));
+        std::fs::remove_dir_all(&dir).expect("remove day directory");
+    }
+
+    #[test]
+    fn line_chunks_split_whole_lines() {
+        let text: &str = "one\ntwo\nthree\nfour\nfive\nsix\n";
+        for n in 0..10 {
+            assert_chunks(text, n);
+        }
+        assert_eq!(
+            assert_chunks(text, 2),
+            ["one\ntwo\nthree\n", "four\nfive\nsix\n"]
+        );
+        assert_eq!(assert_chunks(text, 1), [text]);
+    }
+
+    #[test]
+    fn line_chunks_keep_a_last_line_without_newline() {
+        let text: &str = "1abc2\npqr3stu8vwx\na1b2c3d4e5f\ntreb7uchet";
+        for n in 1..6 {
+            let chunks: Vec<&str> = assert_chunks(text, n);
+            assert!(chunks.last().is_some_and(|c| c.ends_with("treb7uchet")));
+        }
+    }
+
+    #[test]
+    fn line_chunks_never_exceed_the_lines() {
+        let text: &str = "a\nb\nc\n";
+        for n in [3, 4, 10, 1000] {
+            assert_eq!(assert_chunks(text, n), ["a\n", "b\n", "c\n"]);
+        }
+    }
+
+    #[test]
+    fn line_chunks_keep_a_long_line_whole() {
+        let long: String = "x".repeat(10_000);
+        assert_eq!(assert_chunks(&long, 8), [long.as_str()]);
+        let text: String = format!("a\n{}\nb\n", long);
+        let chunks: Vec<&str> = assert_chunks(&text, 8);
+        assert!(chunks.iter().any(|c| c.contains(&long)));
+    }
+
+    #[test]
+    fn line_chunks_of_empty_input_are_empty() {
+        for n in [0, 1, 4] {
+            assert!(assert_chunks("", n).is_empty());
+        }
+        assert_eq!(assert_chunks("\n", 4), ["\n"]);
+        assert_eq!(assert_chunks("\n\n\n", 4), ["\n", "\n", "\n"]);
+    }
+}
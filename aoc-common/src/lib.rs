@@ -0,0 +1,35 @@
+//!
+//! FILENAME
+//!   AdventOfCode/aoc-common/src/lib.rs
+//!
+//! DESCRIPTION
+//!   Shared helpers for Advent of Code solutions: terminal colors, input reading, the
+//!   command-line interface common to every day, the `Solution` trait each day implements, the
+//!   shared error type, and benchmark statistics.
+//!
+//! AUTHOR
+//!   Adam Erickson, PhD
+//!
+//! DATE
+//!   2023-12-02
+//!
+//! NOTES
+//!   Each `YYYY/day_NN/rust` crate depends on this crate instead of copying the helpers.
+//!
+
+pub mod bench;
+pub mod cli;
+pub mod color;
+pub mod error;
+pub mod io;
+pub mod solution;
+
+pub use bench::{format_duration, measure, StageStats, Stats};
+pub use cli::{banner, help, problem, Arg, CliError, Matches, Opt, Spec};
+pub use color::colorize;
+pub use error::{Error, Location, Result};
+pub use io::{
+    day_dir, examples, line_chunks, map_input, read_input, read_lines, workspace_root, Buffer,
+    Source,
+};
+pub use solution::{Accumulator, Answer, Part, Runner, Solution, PARTS};
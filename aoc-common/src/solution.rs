@@ -0,0 +1,212 @@
+//!
+//! Solution interface implemented by every day.
+//!
+
+use std::fmt;
+
+use crate::bench::{measure, StageStats};
+use crate::error::Result;
+
+///
+/// Variables, Data Structures, Types
+///
+
+/* Puzzle part. Each day unlocks part two after part one is solved. */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Part {
+    One,
+    Two,
+}
+
+/* Both parts in puzzle order. */
+pub static PARTS: [Part; 2] = [Part::One, Part::Two];
+
+/* Puzzle answer as submitted on the website. */
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Answer {
+    Int(i64),
+    Text(String),
+    Unsolved,
+}
+
+/*
+ * A day's solution split into stages. `parse` converts the raw puzzle input into `Input` once,
+ * and both parts are computed from the parsed input. `Input` may borrow from the raw input, such
+ * as line slices of it, so parsing need not copy. Stages take `&self` so a day can carry
+ * configuration, such as the line summation method of day 1.
+ */
+pub trait Solution {
+    type Input<'a>;
+
+    fn parse<'a>(&self, input: &'a str) -> Result<Self::Input<'a>>;
+
+    fn part_one(&self, input: &Self::Input<'_>) -> Result<Answer>;
+
+    fn part_two(&self, input: &Self::Input<'_>) -> Result<Answer>;
+
+    /*
+     * Alternative configurations of this solution, labeled for benchmarking head to head.
+     * Days with a single implementation return none.
+     */
+    fn variants(&self) -> Vec<(String, Box<dyn Runner>)> {
+        return Vec::new();
+    }
+
+    /*
+     * Line-by-line form of one part, for input that arrives while the solution runs. Days whose
+     * answer depends on the whole input at once return none.
+     */
+    fn accumulator(&self, _part: Part) -> Option<Box<dyn Accumulator>> {
+        return None;
+    }
+
+    /* Parse the input and compute the answer to one part. */
+    fn solve(&self, input: &str, part: Part) -> Result<Answer> {
+        let parsed: Self::Input<'_> = self.parse(input)?;
+        return match part {
+            Part::One => self.part_one(&parsed),
+            Part::Two => self.part_two(&parsed),
+        };
+    }
+}
+
+/*
+ * Running answer to one part, fed the input a line at a time. `current` after pushing every line
+ * of an input equals the answer `Solution::solve` computes for it.
+ */
+pub trait Accumulator {
+    /* Consume the next line of input, without its line terminator. */
+    fn push_line(&mut self, line: &str) -> Result<()>;
+
+    /* Answer for the lines pushed so far. */
+    fn current(&self) -> Answer;
+}
+
+/*
+ * Object-safe view of a `Solution`. Days have different `Input` types, so tooling that drives
+ * any day, such as the `aoc` runner, holds them as `Box<dyn Runner>`.
+ */
+pub trait Runner {
+    /*
+     * Parse the input once and compute the answer to each requested part. Each part succeeds or
+     * fails on its own, so an input that only one part can solve still answers the other.
+     */
+    fn run(&self, input: &str, parts: &[Part]) -> Result<Vec<Result<Answer>>>;
+
+    /* Time parsing and each part separately. See `bench::measure`. */
+    fn bench(&self, input: &str, warmup: usize, iterations: usize) -> Result<StageStats>;
+
+    /* See `Solution::variants`. */
+    fn variants(&self) -> Vec<(String, Box<dyn Runner>)>;
+
+    /* See `Solution::accumulator`. */
+    fn accumulator(&self, part: Part) -> Option<Box<dyn Accumulator>>;
+}
+
+///
+/// Functions
+///
+
+impl Part {
+    /* Part number as written in the puzzle statement. */
+    pub fn number(self) -> u8 {
+        return match self {
+            Part::One => 1,
+            Part::Two => 2,
+        };
+    }
+
+    /* Map a part number to a part. */
+    pub fn from_number(n: u8) -> Option<Part> {
+        return match n {
+            1 => Some(Part::One),
+            2 => Some(Part::Two),
+            _ => None,
+        };
+    }
+}
+
+impl<S: Solution> Runner for S {
+    fn run(&self, input: &str, parts: &[Part]) -> Result<Vec<Result<Answer>>> {
+        let parsed: S::Input<'_> = self.parse(input)?;
+        let mut answers: Vec<Result<Answer>> = Vec::with_capacity(parts.len());
+        for part in parts {
+            answers.push(match part {
+                Part::One => self.part_one(&parsed),
+                Part::Two => self.part_two(&parsed),
+            });
+        }
+        return Ok(answers);
+    }
+
+    fn bench(&self, input: &str, warmup: usize, iterations: usize) -> Result<StageStats> {
+        // run every stage once so errors are reported instead of timed
+        let parsed: S::Input<'_> = self.parse(input)?;
+        self.part_one(&parsed)?;
+        self.part_two(&parsed)?;
+        return Ok(StageStats {
+            parse: measure(warmup, iterations, || self.parse(input)),
+            part_one: measure(warmup, iterations, || self.part_one(&parsed)),
+            part_two: measure(warmup, iterations, || self.part_two(&parsed)),
+        });
+    }
+
+    fn variants(&self) -> Vec<(String, Box<dyn Runner>)> {
+        return Solution::variants(self);
+    }
+
+    fn accumulator(&self, part: Part) -> Option<Box<dyn Accumulator>> {
+        return Solution::accumulator(self, part);
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.number())
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Int(n) => write!(f, "{}", n),
+            Answer::Text(s) => write!(f, "{}", s),
+            Answer::Unsolved => write!(f, "unsolved"),
+        }
+    }
+}
+
+impl From<u32> for Answer {
+    fn from(n: u32) -> Answer {
+        return Answer::Int(i64::from(n));
+    }
+}
+
+impl From<u64> for Answer {
+    /* Integer answer, or its decimal digits if it does not fit an `i64`. */
+    fn from(n: u64) -> Answer {
+        return Answer::from(u128::from(n));
+    }
+}
+
+impl From<u128> for Answer {
+    /* Integer answer, or its decimal digits if it does not fit an `i64`. */
+    fn from(n: u128) -> Answer {
+        return match i64::try_from(n) {
+            Ok(n) => Answer::Int(n),
+            Err(_) => Answer::Text(n.to_string()),
+        };
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(n: i64) -> Answer {
+        return Answer::Int(n);
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Answer {
+        return Answer::Text(s);
+    }
+}
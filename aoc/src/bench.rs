@@ -0,0 +1,145 @@
+//!
+//! `aoc bench`: time the parse, part one, and part two stages of a day in-process, so process
+//! startup and file I/O are excluded. Days with several implementations are compared head to head.
+//!
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use aoc_common::cli;
+use aoc_common::{
+    colorize, day_dir, format_duration, workspace_root, CliError, Matches, Opt, Result, Runner,
+    Source, Spec, StageStats, Stats,
+};
+
+use crate::registry::{self, Entry};
+use crate::run::{heading, DAY, YEAR};
+
+///
+/// Variables, Data Structures, Types
+///
+
+/* Options for `aoc bench`. */
+struct BenchArgs {
+    year: u16,
+    day: u8,
+    warmup: usize,
+    iterations: usize,
+}
+
+/* Command-line interface of `aoc bench`. */
+pub static SPEC: Spec = Spec {
+    name: "aoc bench",
+    about: "Time parse, part 1, and part 2 of every variant of a day in-process.",
+    args: &[YEAR, DAY],
+    options: &[
+        Opt::value(None, "warmup", "N", "Untimed runs before measuring.").default("10"),
+        Opt::value(Some('n'), "iterations", "N", "Timed runs per stage.").default("100"),
+        cli::INPUT,
+        cli::TEXT,
+        cli::EXAMPLE,
+        cli::HELP,
+    ],
+    examples: &["aoc bench 2023 1 --iterations 500"],
+};
+
+///
+/// Functions
+///
+
+/*
+ * Check the parsed arguments of `aoc bench`. At least one timed iteration is required.
+ */
+fn parse_args(matches: &Matches) -> std::result::Result<BenchArgs, CliError> {
+    let iterations: usize = matches.value_as("iterations")?.unwrap_or_default();
+    if iterations == 0 {
+        return Err(CliError::InvalidValue {
+            option: "iterations",
+            value: iterations.to_string(),
+            choices: &[],
+        });
+    }
+    return Ok(BenchArgs {
+        year: matches.arg_as(YEAR.name)?.unwrap_or_default(),
+        day: matches.arg_as(DAY.name)?.unwrap_or_default(),
+        warmup: matches.value_as("warmup")?.unwrap_or_default(),
+        iterations,
+    });
+}
+
+/*
+ * Print one stage of every variant as a table. `relative` is the median over the fastest median,
+ * and `speedup` is the median of the first variant, the day's default, over the median.
+ */
+fn print_stage(stage: &str, rows: &[(String, Stats)]) {
+    let fastest: Duration = rows.iter().map(|(_, s)| s.median).min().unwrap_or_default();
+    let baseline: Duration = rows.first().map(|(_, s)| s.median).unwrap_or_default();
+    let width: usize = rows.iter().map(|(n, _)| n.len()).max().unwrap_or(0).max(7);
+    println!("\n{}", colorize(stage, "yellow", true, false));
+    println!(
+        "  {:<width$}  {:>10}  {:>10}  {:>10}  {:>10}  {:>8}  {:>8}",
+        "variant", "median", "p95", "mean", "stddev", "relative", "speedup"
+    );
+    for (name, stats) in rows {
+        let relative: f64 =
+            stats.median.as_secs_f64() / fastest.as_secs_f64().max(f64::MIN_POSITIVE);
+        let speedup: f64 =
+            baseline.as_secs_f64() / stats.median.as_secs_f64().max(f64::MIN_POSITIVE);
+        println!(
+            "  {:<width$}  {:>10}  {:>10}  {:>10}  {:>10}  {:>7.2}x  {:>7.2}x",
+            name,
+            format_duration(stats.median),
+            format_duration(stats.p95),
+            format_duration(stats.mean),
+            format_duration(stats.stddev),
+            relative,
+            speedup
+        );
+    }
+}
+
+/*
+ * Entry point for `aoc bench`.
+ */
+pub fn main(args: &[String]) -> Result<()> {
+    let matches: Matches = match SPEC.parse_command(args)? {
+        Some(matches) => matches,
+        None => return Ok(()),
+    };
+    let args: BenchArgs = parse_args(&matches)?;
+    let entry: &Entry = registry::find(args.year, args.day).ok_or(format!(
+        "no solution registered for {} day {}",
+        args.year, args.day
+    ))?;
+    let root: PathBuf = workspace_root();
+    let dir: PathBuf = day_dir(&root, entry.year, entry.day);
+    let source: Source = Source::from_matches(&matches, &dir)?;
+    let text: String = source.read(&dir)?;
+    let solution: Box<dyn Runner> = (entry.solution)();
+    let mut variants: Vec<(String, Box<dyn Runner>)> = solution.variants();
+    if variants.is_empty() {
+        variants.push((String::from("default"), solution));
+    }
+
+    println!("{}", heading(entry));
+    println!(
+        "warm-up: {}, iterations: {}, variants: {}",
+        args.warmup,
+        args.iterations,
+        variants.len()
+    );
+    let mut results: Vec<(String, StageStats)> = Vec::with_capacity(variants.len());
+    for (name, variant) in variants {
+        let stats: StageStats = variant
+            .bench(&text, args.warmup, args.iterations)
+            .map_err(|e| e.with_path(&source.name()))?;
+        results.push((name, stats));
+    }
+    let stage = |f: fn(&StageStats) -> Stats| -> Vec<(String, Stats)> {
+        return results.iter().map(|(n, s)| (n.clone(), f(s))).collect();
+    };
+    print_stage("parse", &stage(|s| s.parse));
+    print_stage("part 1", &stage(|s| s.part_one));
+    print_stage("part 2", &stage(|s| s.part_two));
+    return Ok(());
+}
@@ -0,0 +1,90 @@
+//!
+//! FILENAME
+//!   AdventOfCode/aoc/src/main.rs
+//!
+//! DESCRIPTION
+//!   Runner for the Advent of Code solutions registered in this workspace.
+//!
+//! AUTHOR
+//!   Adam Erickson, PhD
+//!
+//! DATE
+//!   2023-12-02
+//!
+//! NOTES
+//!   Replaces running `./target/release/main` by hand inside each `YYYY/day_NN/rust` directory.
+//!
+
+mod bench;
+mod new;
+mod parity;
+mod readme;
+mod registry;
+mod run;
+mod verify;
+
+use std::env;
+
+use aoc_common::{help, Result, Spec};
+
+///
+/// Variables, Data Structures, Types
+///
+
+/* Subcommands in the order they are listed in the help text. */
+static COMMANDS: [&Spec; 5] = [
+    &run::SPEC,
+    &new::SPEC,
+    &verify::SPEC,
+    &bench::SPEC,
+    &parity::SPEC,
+];
+
+///
+/// Functions
+///
+
+/*
+ * Help text listing every subcommand with its arguments and examples, generated from their specs.
+ */
+fn help_text() -> String {
+    let mut text: String = String::from(
+        "\nUsage: aoc[EXE] <COMMAND> [OPTIONS]\n\n\
+         Run the Advent of Code solutions registered in this workspace.\n\nCommands:\n",
+    );
+    for spec in COMMANDS {
+        let command: &str = spec.name.trim_start_matches("aoc ");
+        let signature: String = format!("{} {}", command, spec.synopsis());
+        text.push_str(&format!("  {:<24}  {}\n", signature.trim_end(), spec.about));
+    }
+    text.push_str("\nRun `aoc <COMMAND> --help` for the options of a command.\n\nExamples:\n");
+    for spec in COMMANDS {
+        for example in spec.examples {
+            text.push_str(&format!("  $ {}\n", example));
+        }
+    }
+    return text;
+}
+
+///
+/// Main
+///
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let result: Result<()> = match args.first().map(|s| s.as_str()) {
+        Some("run") => run::main(&args[1..]),
+        Some("new") => new::main(&args[1..]),
+        Some("verify") => verify::main(&args[1..]),
+        Some("bench") => bench::main(&args[1..]),
+        Some("parity") => parity::main(&args[1..]),
+        Some("-h") | Some("--help") | None => {
+            help(&help_text());
+            Ok(())
+        }
+        Some(command) => Err(format!("unknown command: {}", command).into()),
+    };
+    if let Err(e) = result {
+        e.exit();
+    }
+}
@@ -0,0 +1,234 @@
+//!
+//! `aoc new`: scaffold a day from the `template/` directory and register it in the workspace.
+//!
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use aoc_common::cli;
+use aoc_common::{day_dir, workspace_root, CliError, Matches, Opt, Result, Spec};
+
+use crate::run::{DAY, YEAR};
+
+///
+/// Variables, Data Structures, Types
+///
+
+/* Options for `aoc new`. */
+struct NewArgs {
+    year: u16,
+    day: u8,
+    title: String,
+}
+
+/* Command-line interface of `aoc new`. */
+pub static SPEC: Spec = Spec {
+    name: "aoc new",
+    about: "Create `<YEAR>/day_<DAY>` from `template/` and register it with the runner.",
+    args: &[YEAR, DAY],
+    options: &[
+        Opt::value(None, "title", "TITLE", "Puzzle title. Required."),
+        cli::HELP,
+    ],
+    examples: &["aoc new 2023 3 --title \"Gear Ratios\""],
+};
+
+/* Template files whose placeholders are substituted. Other files are copied verbatim. */
+static SUBSTITUTED: [&str; 4] = ["README.md", "banner.txt", "rust/lib.rs", "rust/main.rs"];
+
+/* Line in `aoc/Cargo.toml` after which day crates are listed. */
+const DEPENDENCIES_MARKER: &str = "# registered solutions\n";
+
+/* Start of the solution list in `aoc/src/registry.rs`. */
+const REGISTRY_MARKER: &str = "pub static SOLUTIONS: &[Entry] = &[\n";
+
+///
+/// Functions
+///
+
+/*
+ * Check the parsed arguments of `aoc new`. The day must be 1 to 25 and the title is required.
+ */
+fn parse_args(matches: &Matches) -> std::result::Result<NewArgs, CliError> {
+    let year: u16 = matches.arg_as(YEAR.name)?.unwrap_or_default();
+    let day: u8 = matches.arg_as(DAY.name)?.unwrap_or_default();
+    if !(1..=25).contains(&day) {
+        return Err(CliError::InvalidArgument {
+            name: DAY.name,
+            value: day.to_string(),
+        });
+    }
+    let title: String = match matches.value("title") {
+        Some(title) => String::from(title),
+        None => return Err(CliError::MissingValue("title")),
+    };
+    return Ok(NewArgs { year, day, title });
+}
+
+/*
+ * Lowercase snake-case slug of a puzzle title, e.g. "Gear Ratios" -> "gear_ratios".
+ */
+fn slug(title: &str) -> String {
+    let mut slug: String = String::new();
+    for c in title.chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c.to_ascii_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('_') {
+            slug.push('_');
+        }
+    }
+    return slug.trim_end_matches('_').to_string();
+}
+
+/*
+ * Replace the template placeholders in a file's text.
+ */
+fn substitute(text: &str, args: &NewArgs, crate_name: &str) -> String {
+    let day: String = format!("{:02}", args.day);
+    return text
+        .replace("YYYY-MM-DD", &format!("{}-12-{}", args.year, day))
+        .replace("YYYY", &args.year.to_string())
+        .replace("day_NN", &format!("day_{}", day))
+        .replace("December NN", &format!("December {}", day))
+        .replace("Day NN", &format!("Day {}", day))
+        .replace("TITLE", &args.title)
+        .replace("CRATE", crate_name);
+}
+
+/*
+ * Copy a directory tree.
+ */
+fn copy_dir(from: &Path, to: &Path) -> Result<()> {
+    fs::create_dir_all(to)?;
+    for entry in fs::read_dir(from)? {
+        let entry: fs::DirEntry = entry?;
+        let target: PathBuf = to.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir(&entry.path(), &target)?;
+        } else {
+            fs::copy(entry.path(), target)?;
+        }
+    }
+    return Ok(());
+}
+
+/*
+ * Cargo manifest of a generated day crate. The template has none.
+ */
+fn manifest(crate_name: &str) -> String {
+    return format!(
+        r#"[package]
+name = "{name}"
+version.workspace = true
+edition.workspace = true
+authors.workspace = true
+license.workspace = true
+
+[lints]
+workspace = true
+
+[dependencies]
+aoc-common = {{ path = "../../../aoc-common" }}
+
+[lib]
+path = "lib.rs"
+
+[[bin]]
+name = "{name}"
+path = "main.rs"
+"#,
+        name = crate_name
+    );
+}
+
+/*
+ * Insert `text` into the file at `path` before the first line equal to `end` that follows the
+ * `marker` line, i.e. append it to the block or list opened by the marker.
+ */
+fn append_to_block(path: &Path, marker: &str, end: &str, text: &str) -> Result<()> {
+    let contents: String = fs::read_to_string(path)?;
+    let start: usize = contents.find(marker).ok_or(format!(
+        "{} has no `{}` marker",
+        path.display(),
+        marker.trim()
+    ))?;
+    let mut index: usize = start + marker.len();
+    while index < contents.len() {
+        let line_end: usize = contents[index..]
+            .find('\n')
+            .map_or(contents.len(), |i| index + i);
+        if &contents[index..line_end] == end {
+            break;
+        }
+        index = line_end + 1;
+    }
+    let index: usize = index.min(contents.len());
+    let updated: String = format!("{}{}{}", &contents[..index], text, &contents[index..]);
+    fs::write(path, updated)?;
+    return Ok(());
+}
+
+/*
+ * Register the generated crate with the workspace, the runner's dependencies, and the registry.
+ */
+fn register(root: &Path, args: &NewArgs, crate_name: &str) -> Result<()> {
+    let member: String = format!("{}/day_{:02}/rust", args.year, args.day);
+    let workspace: PathBuf = root.join("Cargo.toml");
+    let members: String = fs::read_to_string(&workspace)?;
+    if !members.contains("\"*/day_*/rust\"") && !members.contains(&format!("\"{}\"", member)) {
+        append_to_block(
+            &workspace,
+            "members = [\n",
+            "]",
+            &format!("    \"{}\",\n", member),
+        )?;
+    }
+    append_to_block(
+        &root.join("aoc").join("Cargo.toml"),
+        DEPENDENCIES_MARKER,
+        "",
+        &format!("{} = {{ path = \"../{}\" }}\n", crate_name, member),
+    )?;
+    let entry: String = format!(
+        "    Entry {{\n        year: {},\n        day: {},\n        title: {:?},\n        solution: || Box::new({}::Puzzle::default()),\n    }},\n",
+        args.year, args.day, args.title, crate_name
+    );
+    append_to_block(
+        &root.join("aoc").join("src").join("registry.rs"),
+        REGISTRY_MARKER,
+        "];",
+        &entry,
+    )?;
+    return Ok(());
+}
+
+/*
+ * Entry point for `aoc new`.
+ */
+pub fn main(args: &[String]) -> Result<()> {
+    let matches: Matches = match SPEC.parse_command(args)? {
+        Some(matches) => matches,
+        None => return Ok(()),
+    };
+    let args: NewArgs = parse_args(&matches)?;
+    let root: PathBuf = workspace_root();
+    let dir: PathBuf = day_dir(&root, args.year, args.day);
+    if dir.exists() {
+        return Err(format!("{} already exists", dir.display()).into());
+    }
+    let crate_name: String = format!("day_{:02}_{}", args.day, slug(&args.title));
+
+    // copy the template and fill in its placeholders
+    copy_dir(&root.join("template"), &dir)?;
+    for file in SUBSTITUTED {
+        let path: PathBuf = dir.join(file);
+        let text: String = fs::read_to_string(&path)?;
+        fs::write(&path, substitute(&text, &args, &crate_name))?;
+    }
+    fs::write(dir.join("rust").join("Cargo.toml"), manifest(&crate_name))?;
+
+    register(&root, &args, &crate_name)?;
+    println!("Created {} ({})", dir.display(), crate_name);
+    println!("Run it with: aoc run {} {}", args.year, args.day);
+    return Ok(());
+}
@@ -0,0 +1,369 @@
+//!
+//! `aoc parity`: run every language implementation of a day, with and without `--replace`, and
+//! check that their answers agree with each other and with the README. Toolchains that are not
+//! installed are skipped.
+//!
+
+use std::env;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+use std::time::{Duration, Instant};
+
+use aoc_common::cli;
+use aoc_common::{
+    colorize, day_dir, format_duration, workspace_root, CliError, Matches, Opt, Part, Result, Spec,
+    Stats, PARTS,
+};
+
+use crate::readme;
+use crate::run::{DAY, YEAR};
+
+///
+/// Variables, Data Structures, Types
+///
+
+/* Options for `aoc parity`. */
+struct ParityArgs {
+    year: u16,
+    day: u8,
+    runs: usize,
+}
+
+/* Command-line interface of `aoc parity`. */
+pub static SPEC: Spec = Spec {
+    name: "aoc parity",
+    about: "Check that every language implementation of a day agrees, and time each one.",
+    args: &[YEAR, DAY],
+    options: &[
+        Opt::value(None, "runs", "N", "Runs per implementation and part.").default("3"),
+        cli::HELP,
+    ],
+    examples: &["aoc parity 2023 1"],
+};
+
+/* A runnable implementation found under a day directory. */
+struct Implementation {
+    language: &'static str,
+    dir: PathBuf,
+    program: PathBuf,
+    args: Vec<String>,
+}
+
+/* Result of running one implementation on one part. */
+struct Outcome {
+    language: &'static str,
+    part: Part,
+    answer: std::result::Result<String, String>,
+    time: Option<Stats>,
+}
+
+///
+/// Functions
+///
+
+/*
+ * Check the parsed arguments of `aoc parity`. At least one run per implementation is required.
+ */
+fn parse_args(matches: &Matches) -> std::result::Result<ParityArgs, CliError> {
+    let runs: usize = matches.value_as("runs")?.unwrap_or_default();
+    if runs == 0 {
+        return Err(CliError::InvalidValue {
+            option: "runs",
+            value: runs.to_string(),
+            choices: &[],
+        });
+    }
+    return Ok(ParityArgs {
+        year: matches.arg_as(YEAR.name)?.unwrap_or_default(),
+        day: matches.arg_as(DAY.name)?.unwrap_or_default(),
+        runs,
+    });
+}
+
+/*
+ * Find the first of `names` that is an executable file on the `PATH`.
+ */
+fn find_program(names: &[&str]) -> Option<PathBuf> {
+    let path = env::var_os("PATH")?;
+    for name in names {
+        for dir in env::split_paths(&path) {
+            let candidate: PathBuf = dir.join(format!("{}{}", name, env::consts::EXE_SUFFIX));
+            if candidate.is_file() {
+                return Some(candidate);
+            }
+        }
+    }
+    return None;
+}
+
+/*
+ * Run a build command, turning a non-zero exit status into an error with its stderr.
+ */
+fn build(command: &mut Command) -> std::result::Result<(), String> {
+    let output: Output = command.output().map_err(|e| e.to_string())?;
+    if !output.status.success() {
+        let stderr: String = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        return Err(format!(
+            "build failed: {}",
+            stderr.lines().next().unwrap_or("")
+        ));
+    }
+    return Ok(());
+}
+
+/*
+ * Name of the binary declared by a Cargo manifest's `[[bin]]` section.
+ */
+fn bin_name(manifest: &Path) -> Option<String> {
+    let text: String = std::fs::read_to_string(manifest).ok()?;
+    let bin: &str = &text[text.find("[[bin]]")?..];
+    let line: &str = bin.lines().find(|l| l.trim_start().starts_with("name"))?;
+    let name: &str = line.split('=').nth(1)?.trim().trim_matches('"');
+    return Some(String::from(name));
+}
+
+/*
+ * Python implementation: `python/main.py`.
+ */
+fn python(dir: &Path) -> std::result::Result<Implementation, String> {
+    let program: PathBuf = find_program(&["python3", "python"]).ok_or("python not installed")?;
+    return Ok(Implementation {
+        language: "python",
+        dir: dir.to_path_buf(),
+        program,
+        args: vec![String::from("main.py")],
+    });
+}
+
+/*
+ * Julia implementation: `julia/main.jl`.
+ */
+fn julia(dir: &Path) -> std::result::Result<Implementation, String> {
+    let program: PathBuf = find_program(&["julia"]).ok_or("julia not installed")?;
+    return Ok(Implementation {
+        language: "julia",
+        dir: dir.to_path_buf(),
+        program,
+        args: vec![String::from("main.jl")],
+    });
+}
+
+/*
+ * C++ implementation: `cpp/main.cc`, compiled with `$CXX` or the first compiler found.
+ */
+fn cpp(dir: &Path) -> std::result::Result<Implementation, String> {
+    let cxx: PathBuf = env::var("CXX")
+        .ok()
+        .and_then(|cxx| find_program(&[&cxx]))
+        .or_else(|| find_program(&["clang++", "g++", "c++"]))
+        .ok_or("no C++ compiler installed")?;
+    build(
+        Command::new(cxx)
+            .args(["-O2", "-o", "main", "main.cc"])
+            .current_dir(dir),
+    )?;
+    return Ok(Implementation {
+        language: "c++",
+        dir: dir.to_path_buf(),
+        program: dir.join(format!("main{}", env::consts::EXE_SUFFIX)),
+        args: Vec::new(),
+    });
+}
+
+/*
+ * Rust implementation: the day crate, built in release mode into the workspace target directory.
+ */
+fn rust(root: &Path, dir: &Path) -> std::result::Result<Implementation, String> {
+    let cargo: PathBuf = find_program(&["cargo"]).ok_or("cargo not installed")?;
+    let manifest: PathBuf = dir.join("Cargo.toml");
+    let bin: String = bin_name(&manifest).ok_or("no [[bin]] in Cargo.toml")?;
+    build(
+        Command::new(cargo)
+            .args(["build", "--release", "--quiet", "--manifest-path"])
+            .arg(&manifest),
+    )?;
+    let target: PathBuf = env::var_os("CARGO_TARGET_DIR")
+        .map(PathBuf::from)
+        .unwrap_or(root.join("target"));
+    return Ok(Implementation {
+        language: "rust",
+        dir: dir.to_path_buf(),
+        program: target
+            .join("release")
+            .join(format!("{}{}", bin, env::consts::EXE_SUFFIX)),
+        args: Vec::new(),
+    });
+}
+
+/*
+ * Find and build the implementations of a day. Missing toolchains are returned as skipped.
+ */
+fn discover(root: &Path, day: &Path) -> (Vec<Implementation>, Vec<(&'static str, String)>) {
+    let mut found: Vec<Implementation> = Vec::new();
+    let mut skipped: Vec<(&'static str, String)> = Vec::new();
+    let candidates: [(&'static str, &str, &str); 4] = [
+        ("python", "python", "main.py"),
+        ("julia", "julia", "main.jl"),
+        ("c++", "cpp", "main.cc"),
+        ("rust", "rust", "Cargo.toml"),
+    ];
+    for (language, subdir, source) in candidates {
+        let dir: PathBuf = day.join(subdir);
+        if !dir.join(source).is_file() {
+            continue;
+        }
+        let implementation = match language {
+            "python" => python(&dir),
+            "julia" => julia(&dir),
+            "c++" => cpp(&dir),
+            _ => rust(root, &dir),
+        };
+        match implementation {
+            Ok(i) => found.push(i),
+            Err(reason) => skipped.push((language, reason)),
+        }
+    }
+    return (found, skipped);
+}
+
+/*
+ * Run an implementation `runs` times on one part. The answer is the last line of its output.
+ */
+fn run(implementation: &Implementation, part: Part, runs: usize) -> Outcome {
+    let mut samples: Vec<Duration> = Vec::with_capacity(runs);
+    let mut answer: std::result::Result<String, String> = Err(String::from("not run"));
+    for _ in 0..runs {
+        let mut command: Command = Command::new(&implementation.program);
+        command
+            .args(&implementation.args)
+            .current_dir(&implementation.dir);
+        if part == Part::Two {
+            command.arg("--replace");
+        }
+        let start: Instant = Instant::now();
+        let output: std::io::Result<Output> = command.output();
+        samples.push(start.elapsed());
+        answer = match output {
+            Err(e) => Err(e.to_string()),
+            Ok(o) if !o.status.success() => Err(format!("exited with {}", o.status)),
+            Ok(o) => {
+                let stdout: String = String::from_utf8_lossy(&o.stdout).to_string();
+                match stdout.lines().rev().find(|l| !l.trim().is_empty()) {
+                    Some(line) => Ok(String::from(line.trim())),
+                    None => Err(String::from("no output")),
+                }
+            }
+        };
+        if answer.is_err() {
+            break;
+        }
+    }
+    let time: Option<Stats> = match answer {
+        Ok(_) => Some(Stats::from_samples(&mut samples)),
+        Err(_) => None,
+    };
+    return Outcome {
+        language: implementation.language,
+        part,
+        answer,
+        time,
+    };
+}
+
+/*
+ * Entry point for `aoc parity`. Fails if any implementation errors, disagrees with another, or
+ * disagrees with the README.
+ */
+pub fn main(args: &[String]) -> Result<()> {
+    let matches: Matches = match SPEC.parse_command(args)? {
+        Some(matches) => matches,
+        None => return Ok(()),
+    };
+    let args: ParityArgs = parse_args(&matches)?;
+    let root: PathBuf = workspace_root();
+    let dir: PathBuf = day_dir(&root, args.year, args.day);
+    if !dir.is_dir() {
+        return Err(format!("{} does not exist", dir.display()).into());
+    }
+    let expected: Vec<Option<String>> = readme::read_answers(&dir).unwrap_or_default();
+    let (implementations, skipped) = discover(&root, &dir);
+    for (language, reason) in &skipped {
+        println!(
+            "{}",
+            colorize(
+                &format!("skipping {}: {}", language, reason),
+                "yellow",
+                false,
+                false
+            )
+        );
+    }
+    if implementations.is_empty() {
+        return Err(format!("no runnable implementations under {}", dir.display()).into());
+    }
+
+    let title: String = format!(
+        "{} Day {:02}: parity over {} run(s)",
+        args.year, args.day, args.runs
+    );
+    println!("{}", colorize(&title, "cyan", true, false));
+    println!(
+        "  {:<8}  {:>4}  {:>12}  {:>12}  {:<10}  {:>10}  {:>10}",
+        "language", "part", "answer", "expected", "status", "median", "min"
+    );
+    let mut failures: usize = 0;
+    for part in PARTS {
+        let accepted: Option<&str> = readme::answer(&expected, part);
+        let outcomes: Vec<Outcome> = implementations
+            .iter()
+            .map(|i| run(i, part, args.runs))
+            .collect();
+        // without a recorded answer, implementations are compared to the first successful one
+        let reference: Option<String> = accepted.map(String::from).or_else(|| {
+            outcomes
+                .iter()
+                .find_map(|o| o.answer.as_ref().ok().cloned())
+        });
+        for outcome in &outcomes {
+            let (answer, status): (String, String) = match &outcome.answer {
+                Err(e) => {
+                    failures += 1;
+                    (
+                        String::from("-"),
+                        colorize(&format!("error: {}", e), "red", true, false),
+                    )
+                }
+                Ok(a) if Some(a) == reference.as_ref() => (
+                    a.clone(),
+                    colorize(&format!("{:<10}", "pass"), "green", true, false),
+                ),
+                Ok(a) => {
+                    failures += 1;
+                    (
+                        a.clone(),
+                        colorize(&format!("{:<10}", "FAIL"), "red", true, false),
+                    )
+                }
+            };
+            let (median, min): (String, String) = match outcome.time {
+                Some(t) => (format_duration(t.median), format_duration(t.min)),
+                None => (String::from("-"), String::from("-")),
+            };
+            println!(
+                "  {:<8}  {:>4}  {:>12}  {:>12}  {}  {:>10}  {:>10}",
+                outcome.language,
+                outcome.part,
+                answer,
+                accepted.unwrap_or("-"),
+                status,
+                median,
+                min
+            );
+        }
+    }
+    if failures > 0 {
+        return Err(format!("{} implementation run(s) failed or disagreed", failures).into());
+    }
+    println!("all {} implementations agree", implementations.len());
+    return Ok(());
+}
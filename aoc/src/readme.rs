@@ -0,0 +1,55 @@
+//!
+//! Accepted answers recorded in each day's `README.md` as `**ANSWER**: `...`` markers.
+//!
+
+use std::path::{Path, PathBuf};
+
+use aoc_common::{Error, Part, Result};
+
+///
+/// Variables, Data Structures, Types
+///
+
+/* Marker preceding an accepted answer. */
+const MARKER: &str = "**ANSWER**:";
+
+///
+/// Functions
+///
+
+/*
+ * Parse the answer markers of a README in order. Part one is the first marker and part two
+ * the second. An empty marker (``) means the part has no accepted answer yet.
+ */
+pub fn parse_answers(text: &str) -> Vec<Option<String>> {
+    let mut answers: Vec<Option<String>> = Vec::new();
+    for line in text.lines() {
+        if let Some(rest) = line.trim().strip_prefix(MARKER) {
+            let value: &str = rest.trim().trim_matches('`').trim();
+            answers.push(if value.is_empty() {
+                None
+            } else {
+                Some(String::from(value))
+            });
+        }
+    }
+    return answers;
+}
+
+/*
+ * Read the accepted answers from `<dir>/README.md`.
+ */
+pub fn read_answers(dir: &Path) -> Result<Vec<Option<String>>> {
+    let path: PathBuf = dir.join("README.md");
+    let text: String =
+        std::fs::read_to_string(&path).map_err(|e| Error::from(e).with_path(&path))?;
+    return Ok(parse_answers(&text));
+}
+
+/*
+ * Accepted answer of one part, if recorded.
+ */
+pub fn answer(answers: &[Option<String>], part: Part) -> Option<&str> {
+    let index: usize = usize::from(part.number()) - 1;
+    return answers.get(index).and_then(|a| a.as_deref());
+}
@@ -0,0 +1,51 @@
+//!
+//! Registry of solved days. Every day crate listed in `aoc/Cargo.toml` has an entry here.
+//!
+
+use aoc_common::Runner;
+
+///
+/// Variables, Data Structures, Types
+///
+
+/* Registered solution. */
+pub struct Entry {
+    pub year: u16,
+    pub day: u8,
+    pub title: &'static str,
+    pub solution: fn() -> Box<dyn Runner>,
+}
+
+/*
+ * Registered solutions. `aoc new` appends an entry for each generated day, so the list keeps one
+ * entry per block regardless of its length.
+ */
+#[rustfmt::skip]
+pub static SOLUTIONS: &[Entry] = &[
+    Entry {
+        year: 2023,
+        day: 1,
+        title: "Trebuchet?!",
+        solution: || Box::new(day_01_trebuchet::Trebuchet::default()),
+    },
+];
+
+///
+/// Functions
+///
+
+/*
+ * Find the solution registered for a year and day.
+ */
+pub fn find(year: u16, day: u8) -> Option<&'static Entry> {
+    return SOLUTIONS.iter().find(|e| e.year == year && e.day == day);
+}
+
+/*
+ * All solutions registered for a year, in day order.
+ */
+pub fn year(year: u16) -> Vec<&'static Entry> {
+    let mut entries: Vec<&'static Entry> = SOLUTIONS.iter().filter(|e| e.year == year).collect();
+    entries.sort_by_key(|e| e.day);
+    return entries;
+}
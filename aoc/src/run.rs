@@ -0,0 +1,286 @@
+//!
+//! `aoc run`: solve one day, or every solved day of a year, against its `input.txt`.
+//!
+
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::Duration;
+
+use aoc_common::cli;
+use aoc_common::{
+    colorize, day_dir, workspace_root, Accumulator, Answer, Arg, Buffer, CliError, Error, Matches,
+    Opt, Part, Result, Runner, Source, Spec, PARTS,
+};
+
+use crate::registry::{self, Entry};
+
+///
+/// Variables, Data Structures, Types
+///
+
+/* Options for `aoc run`. */
+struct RunArgs {
+    year: u16,
+    day: Option<u8>,
+    all: bool,
+    parts: Vec<Part>,
+    follow: bool,
+}
+
+/* How long `--follow` waits before looking for more data at the end of a file. */
+static FOLLOW_POLL: Duration = Duration::from_millis(250);
+
+/* Arguments shared by the commands that take a day. */
+pub const YEAR: Arg = Arg::required("YEAR", "Puzzle year, e.g. 2023.");
+pub const DAY: Arg = Arg::required("DAY", "Puzzle day, 1 to 25.");
+
+/* Command-line interface of `aoc run`. */
+pub static SPEC: Spec = Spec {
+    name: "aoc run",
+    about: "Solve one day, or every registered day of a year, against its `input.txt`.",
+    args: &[
+        YEAR,
+        Arg::optional("DAY", "Puzzle day, 1 to 25. Omitted with `--all`."),
+    ],
+    options: &[
+        Opt::flag(
+            Some('a'),
+            "all",
+            "Solve every registered day of the year in order.",
+        ),
+        Opt::value(None, "part", "N", "Puzzle part. Defaults to both.").choices(&["1", "2"]),
+        cli::INPUT,
+        cli::TEXT,
+        cli::EXAMPLE,
+        Opt::flag(
+            Some('f'),
+            "follow",
+            "Print updated answers as lines arrive on stdin or are appended to the input.",
+        ),
+        cli::HELP,
+    ],
+    examples: &[
+        "aoc run 2023 1",
+        "aoc run 2023 1 --part 2",
+        "aoc run 2023 --all",
+        "aoc run 2023 1 --part 2 --example 2",
+        "echo two1nine | aoc run 2023 1 --part 2 --input -",
+        "tail -f calibration.log | aoc run 2023 1 --follow --input -",
+    ],
+};
+
+///
+/// Functions
+///
+
+/*
+ * Check the parsed arguments of `aoc run`. A day and `--all` are exclusive, only examples can be
+ * combined with `--all`, and `--follow` reads a file or stdin.
+ */
+fn parse_args(matches: &Matches) -> Result<RunArgs> {
+    let year: u16 = matches.arg_as(YEAR.name)?.unwrap_or_default();
+    let day: Option<u8> = matches.arg_as(DAY.name)?;
+    let all: bool = matches.flag("all");
+    let follow: bool = matches.flag("follow");
+    let parts: Vec<Part> = match matches.value("part") {
+        Some(_) => vec![matches.part()],
+        None => PARTS.to_vec(),
+    };
+    let input: Option<&'static str> = [cli::INPUT.long, cli::TEXT.long, cli::EXAMPLE.long]
+        .into_iter()
+        .find(|long| matches.value(long).is_some());
+    if all == day.is_some() {
+        return Err(Error::Usage("expected either <DAY> or --all".into()));
+    }
+    if all && input.is_some_and(|long| long != cli::EXAMPLE.long) {
+        return Err(Error::Usage(
+            "only --example can be combined with --all".into(),
+        ));
+    }
+    if follow && all {
+        return Err(CliError::Conflict("follow", "all").into());
+    }
+    if follow && input.is_some_and(|long| long != cli::INPUT.long) {
+        return Err(Error::Usage("--follow reads a file or stdin".into()));
+    }
+    return Ok(RunArgs {
+        year,
+        day,
+        all,
+        parts,
+        follow,
+    });
+}
+
+/*
+ * Read the committed `input.txt` of a registered day.
+ */
+pub fn read_input(root: &Path, entry: &Entry) -> Result<String> {
+    let path: PathBuf = day_dir(root, entry.year, entry.day).join("input.txt");
+    return aoc_common::read_input(&path);
+}
+
+/*
+ * Title line printed before a day's answers.
+ */
+pub fn heading(entry: &Entry) -> String {
+    let title: String = format!("{} Day {:02}: {}", entry.year, entry.day, entry.title);
+    return colorize(&title, "cyan", true, false);
+}
+
+/*
+ * Answer line of one part, or its error in red. The full diagnostic of a failed part is printed
+ * when the run ends.
+ */
+fn part_line(part: Part, answer: &Result<Answer>) -> String {
+    return match answer {
+        Ok(answer) => format!("Part {}: {}", part, answer),
+        Err(e) => {
+            let message: String = format!("error: {}", e);
+            format!("Part {}: {}", part, colorize(&message, "red", true, false))
+        }
+    };
+}
+
+/*
+ * Run one registered day and print its answers. A failed part does not hide the others; the
+ * first failure is returned after every part is printed.
+ */
+fn run_day(dir: &Path, entry: &Entry, parts: &[Part], source: &Source) -> Result<()> {
+    let buffer: Buffer = source.load(dir)?;
+    let text: &str = buffer.text().map_err(|e| e.with_path(&source.name()))?;
+    let solution: Box<dyn Runner> = (entry.solution)();
+    let answers: Vec<Result<Answer>> = solution
+        .run(text, parts)
+        .map_err(|e| e.with_path(&source.name()))?;
+    println!("{}", heading(entry));
+    let mut failure: Option<Error> = None;
+    for (part, answer) in parts.iter().zip(answers) {
+        let answer: Result<Answer> = answer.map_err(|e| e.with_path(&source.name()));
+        println!("  {}", part_line(*part, &answer));
+        if let Err(e) = answer {
+            failure.get_or_insert(e);
+        }
+    }
+    return match failure {
+        Some(e) => Err(e),
+        None => Ok(()),
+    };
+}
+
+/*
+ * Solve a day line by line as its input arrives, printing the answers after each batch of lines.
+ * Stdin is read until it closes. A file is read from the start and then watched for appended
+ * lines until interrupted; a trailing line without a newline waits for the rest of the line.
+ * A part that fails stops taking lines and shows its error while the other parts go on; the first
+ * failure is returned once the input ends, or as soon as every part has failed.
+ */
+fn follow_day(entry: &Entry, parts: &[Part], source: &Source) -> Result<()> {
+    let solution: Box<dyn Runner> = (entry.solution)();
+    let mut accumulators: Vec<Box<dyn Accumulator>> = Vec::with_capacity(parts.len());
+    for part in parts {
+        match solution.accumulator(*part) {
+            Some(accumulator) => accumulators.push(accumulator),
+            None => {
+                return Err(format!(
+                    "{} day {} cannot follow its input: part {} needs the whole input",
+                    entry.year, entry.day, part
+                )
+                .into())
+            }
+        }
+    }
+    let (reader, growing): (Box<dyn Read>, bool) = match source {
+        Source::File(path) => {
+            let file: File = File::open(path).map_err(|e| Error::Io {
+                path: Some(path.clone()),
+                source: e,
+            })?;
+            (Box::new(file), true)
+        }
+        _ => (Box::new(io::stdin()), false),
+    };
+    let mut reader: BufReader<Box<dyn Read>> = BufReader::new(reader);
+    let mut line: String = String::new();
+    let mut answers: Vec<Result<Answer>> = parts.iter().map(|_| Ok(Answer::Unsolved)).collect();
+    let mut pending: bool = false; // lines pushed since the answers were last printed
+    println!("{}", heading(entry));
+    loop {
+        let n: usize = reader.read_line(&mut line).map_err(|e| Error::Io {
+            path: Some(source.name()),
+            source: e,
+        })?;
+        let complete: bool = line.ends_with('\n') || (n == 0 && !growing);
+        if complete && !line.is_empty() {
+            let text: &str = line.strip_suffix('\n').unwrap_or(&line);
+            let text: &str = text.strip_suffix('\r').unwrap_or(text);
+            for (accumulator, answer) in accumulators.iter_mut().zip(answers.iter_mut()) {
+                if answer.is_ok() {
+                    *answer = accumulator
+                        .push_line(text)
+                        .map(|_| accumulator.current())
+                        .map_err(|e| e.with_path(&source.name()));
+                }
+            }
+            line.clear();
+            pending = true;
+        }
+        let finished: bool = (n == 0 && !growing) || answers.iter().all(|answer| answer.is_err());
+        // print once the data received so far is used up, not after every line
+        if pending && (finished || n == 0 || reader.buffer().is_empty()) {
+            let lines: Vec<String> = parts
+                .iter()
+                .zip(&answers)
+                .map(|(part, answer)| part_line(*part, answer))
+                .collect();
+            println!("  {}", lines.join("  "));
+            pending = false;
+        }
+        if finished {
+            return match answers.into_iter().find_map(|answer| answer.err()) {
+                Some(e) => Err(e),
+                None => Ok(()),
+            };
+        }
+        if n == 0 {
+            thread::sleep(FOLLOW_POLL);
+        }
+    }
+}
+
+/*
+ * Entry point for `aoc run`.
+ */
+pub fn main(args: &[String]) -> Result<()> {
+    let matches: Matches = match SPEC.parse_command(args)? {
+        Some(matches) => matches,
+        None => return Ok(()),
+    };
+    let args: RunArgs = parse_args(&matches)?;
+    let root: PathBuf = workspace_root();
+    let entries: Vec<&Entry> = if args.all {
+        registry::year(args.year)
+    } else {
+        let day: u8 = args.day.unwrap_or_default();
+        match registry::find(args.year, day) {
+            Some(entry) => vec![entry],
+            None => {
+                return Err(format!("no solution registered for {} day {}", args.year, day).into())
+            }
+        }
+    };
+    if entries.is_empty() {
+        return Err(format!("no solutions registered for {}", args.year).into());
+    }
+    for entry in entries {
+        let dir: PathBuf = day_dir(&root, entry.year, entry.day);
+        let source: Source = Source::from_matches(&matches, &dir)?;
+        if args.follow {
+            return follow_day(entry, &args.parts, &source);
+        }
+        run_day(&dir, entry, &args.parts, &source)?;
+    }
+    return Ok(());
+}
@@ -0,0 +1,95 @@
+//!
+//! `aoc verify`: check every registered day against the answers recorded in its README.
+//!
+
+use std::path::PathBuf;
+
+use aoc_common::cli;
+use aoc_common::{
+    colorize, day_dir, workspace_root, Answer, Arg, Matches, Result, Runner, Spec, PARTS,
+};
+
+use crate::readme;
+use crate::registry::{self, Entry, SOLUTIONS};
+use crate::run::{heading, read_input};
+
+///
+/// Variables, Data Structures, Types
+///
+
+/* Command-line interface of `aoc verify`. */
+pub static SPEC: Spec = Spec {
+    name: "aoc verify",
+    about: "Check answers against the `**ANSWER**` lines of each README.",
+    args: &[
+        Arg::optional("YEAR", "Puzzle year. Defaults to every year."),
+        Arg::optional("DAY", "Puzzle day. Defaults to every day of the year."),
+    ],
+    options: &[cli::HELP],
+    examples: &["aoc verify", "aoc verify 2023"],
+};
+
+///
+/// Functions
+///
+
+/*
+ * Select the days to verify from the parsed arguments.
+ */
+fn select(matches: &Matches) -> Result<Vec<&'static Entry>> {
+    let year: Option<u16> = matches.arg_as("YEAR")?;
+    let day: Option<u8> = matches.arg_as("DAY")?;
+    let entries: Vec<&'static Entry> = match (year, day) {
+        (Some(y), Some(d)) => registry::find(y, d).into_iter().collect(),
+        (Some(y), None) => registry::year(y),
+        _ => SOLUTIONS.iter().collect(),
+    };
+    if entries.is_empty() {
+        return Err("no registered solutions match".into());
+    }
+    return Ok(entries);
+}
+
+/*
+ * Entry point for `aoc verify`. Fails if any computed answer differs from the README.
+ */
+pub fn main(args: &[String]) -> Result<()> {
+    let matches: Matches = match SPEC.parse_command(args)? {
+        Some(matches) => matches,
+        None => return Ok(()),
+    };
+    let entries: Vec<&Entry> = select(&matches)?;
+    let root: PathBuf = workspace_root();
+    let mut failures: usize = 0;
+    let mut checked: usize = 0;
+    for entry in entries {
+        let dir: PathBuf = day_dir(&root, entry.year, entry.day);
+        let expected: Vec<Option<String>> = readme::read_answers(&dir)?;
+        let text: String = read_input(&root, entry)?;
+        let solution: Box<dyn Runner> = (entry.solution)();
+        let answers: Vec<Result<Answer>> = solution.run(&text, &PARTS)?;
+        println!("{}", heading(entry));
+        for (part, answer) in PARTS.iter().zip(answers) {
+            let actual: String = answer?.to_string();
+            let status: String = match readme::answer(&expected, *part) {
+                None => colorize("skip (no recorded answer)", "yellow", false, false),
+                Some(accepted) if accepted == actual => {
+                    checked += 1;
+                    colorize("pass", "green", true, false)
+                }
+                Some(accepted) => {
+                    checked += 1;
+                    failures += 1;
+                    let message: String = format!("FAIL (expected {})", accepted);
+                    colorize(&message, "red", true, false)
+                }
+            };
+            println!("  Part {}: {} {}", part, actual, status);
+        }
+    }
+    if failures > 0 {
+        return Err(format!("{} of {} answers did not match", failures, checked).into());
+    }
+    println!("{} answers verified", checked);
+    return Ok(());
+}
@@ -0,0 +1,51 @@
+//!
+//! FILENAME
+//!   AdventOfCode/YYYY/day_NN/rust/lib.rs
+//!
+//! DESCRIPTION
+//!   December NN: TITLE
+//!
+//! AUTHOR
+//!   Adam Erickson, PhD
+//!
+//! DATE
+//!   YYYY-MM-DD
+//!
+//! NOTES
+//!   -
+//!
+
+#![allow(dead_code)]
+
+use aoc_common::{Answer, Result, Solution};
+
+///
+/// Variables, Data Structures, Types
+///
+
+/* Day solution. Add fields for any configuration selected on the command line. */
+#[derive(Default)]
+pub struct Puzzle {
+    pub verbose: bool,
+}
+
+///
+/// Functions
+///
+
+impl Solution for Puzzle {
+    type Input<'a> = Vec<&'a str>;
+
+    /* Split the puzzle input into lines borrowed from it. */
+    fn parse<'a>(&self, input: &'a str) -> Result<Vec<&'a str>> {
+        return Ok(input.lines().collect());
+    }
+
+    fn part_one(&self, _input: &Vec<&str>) -> Result<Answer> {
+        return Ok(Answer::Unsolved);
+    }
+
+    fn part_two(&self, _input: &Vec<&str>) -> Result<Answer> {
+        return Ok(Answer::Unsolved);
+    }
+}